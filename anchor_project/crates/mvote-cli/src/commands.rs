@@ -0,0 +1,439 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use mvote::instructions::PassRules;
+use mvote::state::{Config, Poll, Quorum, VoteMode, VotingStyle};
+use mvote_client::format::{format_tokens, parse_tokens};
+use mvote_client::rpc::{resolve_url, unix_now, RpcClient};
+use mvote_client::{accounts, export, instructions, math, pda};
+use serde_json::{json, Value};
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_signer::Signer;
+
+use crate::render;
+use crate::{Cli, Command, ExportFormat};
+
+/// Command result in both output formats
+pub struct Report {
+    pub human: String,
+    pub json: Value,
+}
+
+pub fn run(cli: &Cli) -> Result<Report> {
+    let rpc = RpcClient::new(resolve_url(&cli.url));
+
+    match &cli.command {
+        Command::Initialize => initialize(&rpc, &load_signer(cli)?),
+        Command::Buy {
+            amount,
+            max_lamports,
+        } => buy(&rpc, &load_signer(cli)?, amount, *max_lamports),
+        Command::CreatePoll {
+            question,
+            options,
+            mode,
+            style,
+            duration,
+            start_time,
+            pass_threshold_bps,
+            quorum_votes,
+            quorum_tokens,
+        } => create_poll(
+            &rpc,
+            &load_signer(cli)?,
+            question,
+            options,
+            (*mode).into(),
+            (*style).into(),
+            *duration,
+            *start_time,
+            pass_rules(*pass_threshold_bps, *quorum_votes, quorum_tokens.as_deref())?,
+        ),
+        Command::Vote {
+            poll_id,
+            option,
+            tokens,
+        } => vote(&rpc, &load_signer(cli)?, *poll_id, *option, tokens),
+        Command::VoteRanked { poll_id, rankings } => {
+            vote_ranked(&rpc, &load_signer(cli)?, *poll_id, rankings)
+        }
+        Command::VoteApproval { poll_id, options } => {
+            vote_approval(&rpc, &load_signer(cli)?, *poll_id, options)
+        }
+        Command::ClosePoll { poll_id } => close_poll(&rpc, &load_signer(cli)?, *poll_id),
+        Command::ShowConfig => show_config(&rpc),
+        Command::ListPolls => list_polls(&rpc),
+        Command::ShowPoll { poll_id } => show_poll(&rpc, *poll_id),
+        Command::Export {
+            poll_id,
+            format,
+            out,
+        } => export(&rpc, *poll_id, *format, out.as_deref()),
+    }
+}
+
+fn initialize(rpc: &RpcClient, admin: &Keypair) -> Result<Report> {
+    let signature =
+        rpc.send_and_confirm(&[instructions::initialize(&admin.pubkey())], admin, &[])?;
+
+    let config = pda::config().0;
+    let token_mint = pda::mint().0;
+    let sol_vault = pda::vault().0;
+
+    Ok(Report {
+        human: format!(
+            "Initialized mvote\nConfig: {}\nToken mint: {}\nSOL vault: {}\nSignature: {}",
+            config, token_mint, sol_vault, signature
+        ),
+        json: json!({
+            "signature": signature,
+            "config": config.to_string(),
+            "token_mint": token_mint.to_string(),
+            "sol_vault": sol_vault.to_string(),
+        }),
+    })
+}
+
+fn buy(
+    rpc: &RpcClient,
+    buyer: &Keypair,
+    amount: &str,
+    max_lamports: Option<u64>,
+) -> Result<Report> {
+    let amount = parse_amount(amount)?;
+    let config = fetch_config(rpc)?;
+
+    // The estimate is exact only at the fixed rate, an oracle price may differ
+    let price_oracle = (config.price_oracle != Pubkey::default()).then_some(config.price_oracle);
+    let estimated_lamports =
+        math::calculate_sol_cost(amount, config.sol_usd_rate, config.tokens_per_usd);
+
+    let signature = rpc.send_and_confirm(
+        &[
+            instructions::create_token_account(&buyer.pubkey(), &buyer.pubkey()),
+            instructions::purchase_tokens(&buyer.pubkey(), amount, max_lamports, price_oracle),
+        ],
+        buyer,
+        &[],
+    )?;
+
+    let mut human = format!("Bought {} mVote", format_tokens(amount));
+    if price_oracle.is_none() {
+        if let Some(lamports) = estimated_lamports {
+            human.push_str(&format!(" for {} lamports", lamports));
+        }
+    }
+    human.push_str(&format!("\nSignature: {}", signature));
+
+    Ok(Report {
+        human,
+        json: json!({
+            "signature": signature,
+            "amount": amount,
+            "sol_cost": price_oracle.is_none().then_some(estimated_lamports).flatten(),
+        }),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_poll(
+    rpc: &RpcClient,
+    creator: &Keypair,
+    question: &str,
+    options: &[String],
+    vote_mode: VoteMode,
+    voting_style: VotingStyle,
+    duration_minutes: u16,
+    start_time: Option<i64>,
+    pass_rules: Option<PassRules>,
+) -> Result<Report> {
+    // The next poll takes the current poll count as its id
+    let poll_id = fetch_config(rpc)?.total_polls_created;
+    let poll = pda::poll(poll_id).0;
+
+    let instruction = instructions::create_poll(
+        &creator.pubkey(),
+        poll_id,
+        question.to_string(),
+        options.to_vec(),
+        vote_mode,
+        voting_style,
+        duration_minutes,
+        start_time,
+        pass_rules,
+    );
+    let signature = rpc.send_and_confirm(&[instruction], creator, &[])?;
+
+    Ok(Report {
+        human: format!(
+            "Created poll {} at {}\nSignature: {}",
+            poll_id, poll, signature
+        ),
+        json: json!({
+            "signature": signature,
+            "poll_id": poll_id,
+            "poll": poll.to_string(),
+        }),
+    })
+}
+
+fn vote(
+    rpc: &RpcClient,
+    voter: &Keypair,
+    poll_id: u64,
+    option: u8,
+    tokens: &str,
+) -> Result<Report> {
+    let token_amount = parse_amount(tokens)?;
+    let poll = fetch_poll(rpc, poll_id)?;
+    let option_name = option_name(&poll, option)?;
+
+    let instruction = instructions::vote(
+        &voter.pubkey(),
+        poll_id,
+        poll.vote_mode,
+        option,
+        token_amount,
+    );
+    let signature = rpc.send_and_confirm(&[instruction], voter, &[])?;
+
+    Ok(Report {
+        human: format!(
+            "Voted for option {} ({}) on poll {}\nSignature: {}",
+            option, option_name, poll_id, signature
+        ),
+        json: json!({
+            "signature": signature,
+            "poll_id": poll_id,
+            "option_index": option,
+            "token_amount": token_amount,
+        }),
+    })
+}
+
+fn vote_ranked(rpc: &RpcClient, voter: &Keypair, poll_id: u64, rankings: &[u8]) -> Result<Report> {
+    let poll = fetch_poll(rpc, poll_id)?;
+    let option_names = rankings
+        .iter()
+        .map(|&option| option_name(&poll, option))
+        .collect::<Result<Vec<_>>>()?;
+
+    let instruction = instructions::vote_ranked(&voter.pubkey(), poll_id, rankings.to_vec());
+    let signature = rpc.send_and_confirm(&[instruction], voter, &[])?;
+
+    Ok(Report {
+        human: format!(
+            "Ranked {} on poll {}\nSignature: {}",
+            option_names.join(" > "),
+            poll_id,
+            signature
+        ),
+        json: json!({
+            "signature": signature,
+            "poll_id": poll_id,
+            "rankings": rankings,
+        }),
+    })
+}
+
+fn vote_approval(rpc: &RpcClient, voter: &Keypair, poll_id: u64, options: &[u8]) -> Result<Report> {
+    let option_mask = approval_mask(options)?;
+    let poll = fetch_poll(rpc, poll_id)?;
+    let option_names = options
+        .iter()
+        .map(|&option| option_name(&poll, option))
+        .collect::<Result<Vec<_>>>()?;
+
+    let instruction = instructions::vote_approval(&voter.pubkey(), poll_id, option_mask);
+    let signature = rpc.send_and_confirm(&[instruction], voter, &[])?;
+
+    Ok(Report {
+        human: format!(
+            "Approved {} on poll {}\nSignature: {}",
+            option_names.join(", "),
+            poll_id,
+            signature
+        ),
+        json: json!({
+            "signature": signature,
+            "poll_id": poll_id,
+            "option_mask": option_mask,
+        }),
+    })
+}
+
+fn close_poll(rpc: &RpcClient, creator: &Keypair, poll_id: u64) -> Result<Report> {
+    let instruction = instructions::close_poll(&creator.pubkey(), poll_id);
+    let signature = rpc.send_and_confirm(&[instruction], creator, &[])?;
+
+    Ok(Report {
+        human: format!("Closed poll {}\nSignature: {}", poll_id, signature),
+        json: json!({ "signature": signature, "poll_id": poll_id }),
+    })
+}
+
+fn show_config(rpc: &RpcClient) -> Result<Report> {
+    let config = fetch_config(rpc)?;
+
+    Ok(Report {
+        human: render::config_human(&config),
+        json: render::config_json(&config),
+    })
+}
+
+fn list_polls(rpc: &RpcClient) -> Result<Report> {
+    // Legacy accounts that have not been migrated do not decode and are skipped
+    let mut polls: Vec<Poll> = rpc
+        .get_program_accounts::<Poll>()?
+        .iter()
+        .filter_map(|(_, data)| accounts::decode_poll(data).ok())
+        .collect();
+    polls.sort_by_key(|poll| poll.id);
+
+    let now = unix_now();
+    Ok(Report {
+        human: render::poll_table(&polls, now),
+        json: Value::Array(
+            polls
+                .iter()
+                .map(|poll| render::poll_json(poll, now))
+                .collect(),
+        ),
+    })
+}
+
+fn show_poll(rpc: &RpcClient, poll_id: u64) -> Result<Report> {
+    let poll = fetch_poll(rpc, poll_id)?;
+    let now = unix_now();
+
+    Ok(Report {
+        human: render::poll_human(&poll, now),
+        json: render::poll_json(&poll, now),
+    })
+}
+
+fn export(
+    rpc: &RpcClient,
+    poll_id: u64,
+    format: ExportFormat,
+    out: Option<&Path>,
+) -> Result<Report> {
+    let export =
+        export::fetch(rpc, poll_id)?.ok_or_else(|| anyhow!("poll {} not found", poll_id))?;
+
+    let (contents, extension) = match format {
+        ExportFormat::Csv => (export.to_csv()?, "csv"),
+        ExportFormat::Json => (export.to_canonical_json(), "json"),
+    };
+    let path = out
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("poll-{}.{}", poll_id, extension)));
+    fs::write(&path, contents).with_context(|| format!("failed to write {}", path.display()))?;
+
+    let result_hash = export.result_hash();
+    let mut human = format!(
+        "Exported poll {} ({} votes) to {}\nResult hash: {}",
+        poll_id,
+        export.votes.len(),
+        path.display(),
+        result_hash
+    );
+    if !export.is_complete() {
+        human.push_str(&format!(
+            "\nWarning: {} of {} votes have a vote record",
+            export.counted_votes(),
+            export.poll.total_votes
+        ));
+    }
+
+    Ok(Report {
+        human,
+        json: json!({
+            "poll_id": poll_id,
+            "path": path.display().to_string(),
+            "votes": export.votes.len(),
+            "archived": export.archived,
+            "is_complete": export.is_complete(),
+            "result_hash": result_hash,
+        }),
+    })
+}
+
+fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+    let data = rpc
+        .get_account_data(&pda::config().0)?
+        .ok_or_else(|| anyhow!("program is not initialized on {}", rpc.url()))?;
+    Ok(accounts::decode_config(&data)?)
+}
+
+fn fetch_poll(rpc: &RpcClient, poll_id: u64) -> Result<Poll> {
+    let data = rpc
+        .get_account_data(&pda::poll(poll_id).0)?
+        .ok_or_else(|| anyhow!("poll {} not found", poll_id))?;
+    accounts::decode_poll(&data).map_err(|err| {
+        anyhow!(
+            "poll {} could not be decoded (run migrate_poll?): {}",
+            poll_id,
+            err
+        )
+    })
+}
+
+/// Build the poll's pass rules from the `create-poll` flags. Without a pass
+/// threshold the poll has no pass rules and any quorum is ignored.
+pub fn pass_rules(
+    pass_threshold_bps: Option<u16>,
+    quorum_votes: Option<u64>,
+    quorum_tokens: Option<&str>,
+) -> Result<Option<PassRules>> {
+    let quorum = match (quorum_votes, quorum_tokens) {
+        (Some(votes), _) => Some(Quorum::Votes(votes)),
+        (None, Some(tokens)) => Some(Quorum::Tokens(parse_amount(tokens)?)),
+        (None, None) => None,
+    };
+
+    Ok(pass_threshold_bps.map(|pass_threshold_bps| PassRules {
+        quorum,
+        pass_threshold_bps,
+    }))
+}
+
+/// Turn the option indices given to `vote-approval` into the ballot's option
+/// mask, where bit `i` approves option `i`
+pub fn approval_mask(options: &[u8]) -> Result<u8> {
+    options.iter().try_fold(0u8, |mask, &option| {
+        let bit = 1u8
+            .checked_shl(option as u32)
+            .ok_or_else(|| anyhow!("invalid option index: {}", option))?;
+        if mask & bit != 0 {
+            return Err(anyhow!("option {} is listed more than once", option));
+        }
+        Ok(mask | bit)
+    })
+}
+
+fn option_name(poll: &Poll, option: u8) -> Result<String> {
+    poll.options
+        .get(option as usize)
+        .cloned()
+        .ok_or_else(|| anyhow!("poll {} has no option {}", poll.id, option))
+}
+
+fn parse_amount(amount: &str) -> Result<u64> {
+    parse_tokens(amount).ok_or_else(|| anyhow!("invalid token amount: {}", amount))
+}
+
+fn load_signer(cli: &Cli) -> Result<Keypair> {
+    let path = match &cli.keypair {
+        Some(path) => path.clone(),
+        None => {
+            let home = std::env::var_os("HOME").context("HOME is not set, pass --keypair")?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+
+    read_keypair_file(&path)
+        .map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
@@ -0,0 +1,202 @@
+// `mvote` command-line tool.
+//
+// Wraps the mvote-client instruction builders and account decoders for
+// operators: one-off admin tasks, buying tokens, creating polls, voting and
+// inspecting on-chain state. Works against any RPC endpoint, including a
+// local validator. Argument parsing and the commands live here so they can be
+// tested; `main.rs` only runs them and prints the report.
+
+pub mod commands;
+mod render;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use mvote::state::{VoteMode, VotingStyle};
+
+#[derive(Parser)]
+#[command(
+    name = "mvote",
+    version,
+    about = "Administer and use the mvote program"
+)]
+pub struct Cli {
+    /// RPC URL or moniker (localnet, devnet, mainnet-beta)
+    #[arg(short = 'u', long, global = true, default_value = "localnet")]
+    pub url: String,
+
+    /// Signer keypair file [default: ~/.config/solana/id.json]
+    #[arg(short = 'k', long, global = true)]
+    pub keypair: Option<PathBuf>,
+
+    /// Output format
+    #[arg(short = 'o', long, global = true, value_enum, default_value_t = OutputFormat::Human)]
+    pub output: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Initialize the program config, token mint and SOL vault (admin)
+    Initialize,
+
+    /// Buy mVote tokens with SOL
+    Buy {
+        /// Amount in mVote, e.g. 10 or 2.5
+        amount: String,
+
+        /// Fail if the purchase costs more than this many lamports
+        #[arg(long)]
+        max_lamports: Option<u64>,
+    },
+
+    /// Create a poll (burns the poll creation cost)
+    CreatePoll {
+        /// Poll question (up to 280 characters)
+        #[arg(long)]
+        question: String,
+
+        /// Poll option, repeated 2-4 times
+        #[arg(long = "option", required = true)]
+        options: Vec<String>,
+
+        /// How tokens turn into votes
+        #[arg(long, value_enum, default_value_t = ModeArg::Fair)]
+        mode: ModeArg,
+
+        /// Ballot type
+        #[arg(long, value_enum, default_value_t = StyleArg::MultipleChoice)]
+        style: StyleArg,
+
+        /// Voting period in minutes (10-1440)
+        #[arg(long, default_value_t = 60)]
+        duration: u16,
+
+        /// Unix timestamp to start voting at (default: now)
+        #[arg(long)]
+        start_time: Option<i64>,
+
+        /// Winner's share needed to pass, in basis points
+        #[arg(long)]
+        pass_threshold_bps: Option<u16>,
+
+        /// Minimum number of votes for the poll to count
+        #[arg(
+            long,
+            conflicts_with = "quorum_tokens",
+            requires = "pass_threshold_bps"
+        )]
+        quorum_votes: Option<u64>,
+
+        /// Minimum tokens spent (in mVote) for the poll to count
+        #[arg(long, requires = "pass_threshold_bps")]
+        quorum_tokens: Option<String>,
+    },
+
+    /// Vote on a binary or multiple-choice poll
+    Vote {
+        poll_id: u64,
+
+        /// Option index, starting at 0
+        option: u8,
+
+        /// Tokens to spend in mVote (ignored by fair-vote polls)
+        #[arg(long, default_value = "0")]
+        tokens: String,
+    },
+
+    /// Cast a ranked ballot on a ranked-choice poll
+    VoteRanked {
+        poll_id: u64,
+
+        /// Option indices from most to least preferred
+        #[arg(required = true)]
+        rankings: Vec<u8>,
+    },
+
+    /// Approve one or more options on an approval poll
+    VoteApproval {
+        poll_id: u64,
+
+        /// Option indices to approve
+        #[arg(required = true)]
+        options: Vec<u8>,
+    },
+
+    /// Close an active poll early (creator)
+    ClosePoll { poll_id: u64 },
+
+    /// Show the program config
+    ShowConfig,
+
+    /// List all polls
+    ListPolls,
+
+    /// Show a poll and its results
+    ShowPoll { poll_id: u64 },
+
+    /// Export a poll's full results, including every vote, for auditing
+    Export {
+        poll_id: u64,
+
+        /// File format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// Output file [default: poll-<id>.<format>]
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ModeArg {
+    Fair,
+    Holding,
+    Escrow,
+    Quadratic,
+}
+
+impl From<ModeArg> for VoteMode {
+    fn from(mode: ModeArg) -> Self {
+        match mode {
+            ModeArg::Fair => VoteMode::FairVote,
+            ModeArg::Holding => VoteMode::HoldingVote,
+            ModeArg::Escrow => VoteMode::EscrowVote,
+            ModeArg::Quadratic => VoteMode::Quadratic,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StyleArg {
+    Binary,
+    MultipleChoice,
+    RankedChoice,
+    Approval,
+}
+
+impl From<StyleArg> for VotingStyle {
+    fn from(style: StyleArg) -> Self {
+        match style {
+            StyleArg::Binary => VotingStyle::Binary,
+            StyleArg::MultipleChoice => VotingStyle::MultipleChoice,
+            StyleArg::RankedChoice => VotingStyle::RankedChoice,
+            StyleArg::Approval => VotingStyle::Approval,
+        }
+    }
+}
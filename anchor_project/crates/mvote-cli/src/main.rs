@@ -0,0 +1,27 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+use mvote_cli::{commands, Cli, OutputFormat};
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match commands::run(&cli) {
+        Ok(report) => {
+            match cli.output {
+                OutputFormat::Human => println!("{}", report.human),
+                OutputFormat::Json => println!("{:#}", report.json),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            match cli.output {
+                OutputFormat::Human => eprintln!("Error: {:#}", err),
+                OutputFormat::Json => {
+                    eprintln!("{:#}", serde_json::json!({ "error": format!("{:#}", err) }))
+                }
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::Write;
+
+use anchor_lang::prelude::Pubkey;
+use mvote::state::{Config, Poll, Quorum};
+use mvote_client::format::{
+    format_tokens, poll_outcome_name, poll_status_name, vote_mode_name, voting_style_name,
+};
+use mvote_client::pda;
+use serde_json::{json, Value};
+
+// Human and JSON renderings of program accounts. JSON keeps raw token units
+// and unix timestamps, human output shows whole mVote.
+
+pub fn config_json(config: &Config) -> Value {
+    json!({
+        "address": pda::config().0.to_string(),
+        "admin": config.admin.to_string(),
+        "pending_admin": config.pending_admin.to_string(),
+        "token_mint": config.token_mint.to_string(),
+        "sol_vault": config.sol_vault.to_string(),
+        "treasury": config.treasury.to_string(),
+        "paused": config.paused,
+        "sol_usd_rate": config.sol_usd_rate,
+        "price_oracle": config.price_oracle.to_string(),
+        "price_feed_id": hex(&config.price_feed_id),
+        "max_price_age": config.max_price_age,
+        "max_price_conf_bps": config.max_price_conf_bps,
+        "tokens_per_usd": config.tokens_per_usd,
+        "daily_purchase_limit": config.daily_purchase_limit,
+        "daily_redeem_limit": config.daily_redeem_limit,
+        "redeem_spread_bps": config.redeem_spread_bps,
+        "poll_creation_cost": config.poll_creation_cost,
+        "vote_cost": config.vote_cost,
+        "total_polls_created": config.total_polls_created,
+        "max_start_lead_time": config.max_start_lead_time,
+        "poll_retention_period": config.poll_retention_period,
+    })
+}
+
+pub fn config_human(config: &Config) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Config:               {}", pda::config().0);
+    let _ = writeln!(out, "Admin:                {}", config.admin);
+    let _ = writeln!(out, "Token mint:           {}", config.token_mint);
+    let _ = writeln!(out, "SOL vault:            {}", config.sol_vault);
+    let _ = writeln!(out, "Treasury:             {}", config.treasury);
+    let _ = writeln!(out, "Paused:               {}", config.paused);
+    let _ = writeln!(
+        out,
+        "SOL/USD rate:         ${}.{:02}",
+        config.sol_usd_rate / 100,
+        config.sol_usd_rate % 100
+    );
+    let _ = writeln!(out, "Price oracle:         {}", config.price_oracle);
+    if config.price_oracle != Pubkey::default() {
+        let _ = writeln!(out, "Price feed id:        {}", hex(&config.price_feed_id));
+    }
+    let _ = writeln!(
+        out,
+        "Daily purchase limit: {} mVote",
+        format_tokens(config.daily_purchase_limit)
+    );
+    let _ = writeln!(
+        out,
+        "Daily redeem limit:   {} mVote",
+        format_tokens(config.daily_redeem_limit)
+    );
+    let _ = writeln!(
+        out,
+        "Poll creation cost:   {} mVote",
+        format_tokens(config.poll_creation_cost)
+    );
+    let _ = writeln!(
+        out,
+        "Vote cost:            {} mVote",
+        format_tokens(config.vote_cost)
+    );
+    let _ = write!(out, "Polls created:        {}", config.total_polls_created);
+    out
+}
+
+pub fn poll_json(poll: &Poll, now: i64) -> Value {
+    json!({
+        "id": poll.id,
+        "address": pda::poll(poll.id).0.to_string(),
+        "creator": poll.creator.to_string(),
+        "question": poll.question,
+        "options": poll.options,
+        "vote_counts": poll.vote_counts,
+        "vote_mode": vote_mode_name(poll.vote_mode),
+        "voting_style": voting_style_name(poll.voting_style),
+        "status": poll_status_name(poll.current_status(now)),
+        "start_time": poll.start_time,
+        "end_time": poll.end_time,
+        "total_votes": poll.total_votes,
+        "total_tokens_spent": poll.total_tokens_spent,
+        "allow_vote_changes": poll.allow_vote_changes,
+        "is_secret": poll.is_secret,
+        "reveal_end_time": poll.reveal_end_time,
+        "winning_option": poll.winning_option,
+        "is_tie": poll.is_tie,
+        "outcome": poll_outcome_name(poll.outcome),
+        "quorum": poll.quorum.map(|quorum| match quorum {
+            Quorum::Votes(votes) => json!({ "votes": votes }),
+            Quorum::Tokens(tokens) => json!({ "tokens": tokens }),
+        }),
+        "pass_threshold_bps": poll.pass_threshold_bps,
+        "finalized_at": poll.finalized_at,
+        "cancelled_at": poll.cancelled_at,
+    })
+}
+
+pub fn poll_human(poll: &Poll, now: i64) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Poll {}: {}", poll.id, poll.question);
+    let _ = writeln!(out, "Address:  {}", pda::poll(poll.id).0);
+    let _ = writeln!(out, "Creator:  {}", poll.creator);
+    let _ = writeln!(
+        out,
+        "Type:     {} / {}{}",
+        voting_style_name(poll.voting_style),
+        vote_mode_name(poll.vote_mode),
+        if poll.is_secret { " (secret)" } else { "" }
+    );
+    let _ = writeln!(
+        out,
+        "Status:   {}",
+        poll_status_name(poll.current_status(now))
+    );
+    let _ = writeln!(out, "Voting:   {} - {}", poll.start_time, poll.end_time);
+    let _ = writeln!(
+        out,
+        "Votes:    {} ({} mVote spent)",
+        poll.total_votes,
+        format_tokens(poll.total_tokens_spent)
+    );
+
+    let total: u64 = poll.vote_counts.iter().sum();
+    for (index, (option, count)) in poll.options.iter().zip(&poll.vote_counts).enumerate() {
+        let share = if total > 0 {
+            *count as f64 * 100.0 / total as f64
+        } else {
+            0.0
+        };
+        let marker = if poll.winning_option == Some(index as u8) {
+            " *"
+        } else {
+            ""
+        };
+        let _ = write!(
+            out,
+            "\n  [{}] {:<30} {:>10} {:>6.1}%{}",
+            index, option, count, share, marker
+        );
+    }
+
+    if poll.pass_threshold_bps > 0 {
+        let _ = write!(out, "\nOutcome:  {}", poll_outcome_name(poll.outcome));
+    }
+    out
+}
+
+pub fn poll_table(polls: &[Poll], now: i64) -> String {
+    if polls.is_empty() {
+        return "No polls".to_string();
+    }
+
+    let mut out = format!(
+        "{:>5}  {:<12}  {:<15}  {:<12}  {:>7}  QUESTION",
+        "ID", "STATUS", "STYLE", "MODE", "VOTES"
+    );
+    for poll in polls {
+        let _ = write!(
+            out,
+            "\n{:>5}  {:<12}  {:<15}  {:<12}  {:>7}  {}",
+            poll.id,
+            poll_status_name(poll.current_status(now)),
+            voting_style_name(poll.voting_style),
+            vote_mode_name(poll.vote_mode),
+            poll.total_votes,
+            poll.question
+        );
+    }
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,143 @@
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use mvote::instructions::PassRules;
+use mvote::state::Quorum;
+use mvote_cli::commands::{approval_mask, pass_rules};
+use mvote_cli::{Cli, Command};
+use mvote_client::rpc::resolve_url;
+
+fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+    let base = ["mvote", "create-poll", "--question", "Lunch?"];
+    let options = ["--option", "Pizza", "--option", "Sushi"];
+    Cli::try_parse_from(base.iter().chain(&options).chain(args))
+}
+
+// Parse `create-poll` with the given extra flags and map them to pass rules
+fn parsed_pass_rules(args: &[&str]) -> Option<PassRules> {
+    match parse(args).unwrap().command {
+        Command::CreatePoll {
+            pass_threshold_bps,
+            quorum_votes,
+            quorum_tokens,
+            ..
+        } => pass_rules(pass_threshold_bps, quorum_votes, quorum_tokens.as_deref()).unwrap(),
+        _ => panic!("expected create-poll"),
+    }
+}
+
+#[test]
+fn cli_definition_is_valid() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn create_poll_without_threshold_has_no_pass_rules() {
+    assert!(parsed_pass_rules(&[]).is_none());
+}
+
+#[test]
+fn create_poll_maps_threshold_without_quorum() {
+    let rules = parsed_pass_rules(&["--pass-threshold-bps", "6667"]).unwrap();
+
+    assert_eq!(rules.pass_threshold_bps, 6667);
+    assert!(rules.quorum.is_none());
+}
+
+#[test]
+fn create_poll_maps_vote_quorum() {
+    let rules =
+        parsed_pass_rules(&["--pass-threshold-bps", "5000", "--quorum-votes", "10"]).unwrap();
+
+    assert_eq!(rules.pass_threshold_bps, 5000);
+    assert!(rules.quorum == Some(Quorum::Votes(10)));
+}
+
+#[test]
+fn create_poll_maps_token_quorum_in_raw_units() {
+    let rules =
+        parsed_pass_rules(&["--pass-threshold-bps", "5000", "--quorum-tokens", "2.5"]).unwrap();
+
+    assert!(rules.quorum == Some(Quorum::Tokens(2_500_000)));
+}
+
+#[test]
+fn create_poll_rejects_invalid_token_quorum() {
+    assert!(pass_rules(Some(5000), None, Some("lots")).is_err());
+}
+
+#[test]
+fn create_poll_quorum_requires_threshold() {
+    for quorum in [["--quorum-votes", "10"], ["--quorum-tokens", "5"]] {
+        let err = parse(&quorum).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+}
+
+#[test]
+fn create_poll_quorums_conflict() {
+    let err = parse(&[
+        "--pass-threshold-bps",
+        "5000",
+        "--quorum-votes",
+        "10",
+        "--quorum-tokens",
+        "5",
+    ])
+    .err()
+    .unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn vote_ranked_takes_rankings_in_order() {
+    let cli = Cli::try_parse_from(["mvote", "vote-ranked", "4", "2", "0", "1"]).unwrap();
+
+    match cli.command {
+        Command::VoteRanked { poll_id, rankings } => {
+            assert_eq!(poll_id, 4);
+            assert_eq!(rankings, [2, 0, 1]);
+        }
+        _ => panic!("expected vote-ranked"),
+    }
+}
+
+#[test]
+fn vote_approval_maps_options_to_mask() {
+    let cli = Cli::try_parse_from(["mvote", "vote-approval", "4", "0", "2"]).unwrap();
+
+    match cli.command {
+        Command::VoteApproval { poll_id, options } => {
+            assert_eq!(poll_id, 4);
+            assert_eq!(approval_mask(&options).unwrap(), 0b101);
+        }
+        _ => panic!("expected vote-approval"),
+    }
+}
+
+#[test]
+fn approval_mask_rejects_repeated_and_out_of_range_options() {
+    assert_eq!(approval_mask(&[3]).unwrap(), 0b1000);
+    assert!(approval_mask(&[1, 1]).is_err());
+    assert!(approval_mask(&[8]).is_err());
+}
+
+#[test]
+fn ranked_and_approval_votes_need_an_option() {
+    for command in ["vote-ranked", "vote-approval"] {
+        let err = Cli::try_parse_from(["mvote", command, "4"]).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+}
+
+#[test]
+fn resolve_url_expands_monikers() {
+    assert_eq!(resolve_url("localnet"), "http://127.0.0.1:8899");
+    assert_eq!(resolve_url("l"), "http://127.0.0.1:8899");
+    assert_eq!(resolve_url("devnet"), "https://api.devnet.solana.com");
+    assert_eq!(resolve_url("m"), "https://api.mainnet-beta.solana.com");
+    assert_eq!(
+        resolve_url("https://rpc.example.com"),
+        "https://rpc.example.com"
+    );
+}
@@ -0,0 +1,31 @@
+use anchor_lang::{AccountDeserialize, Result};
+use mvote::state::{Config, Poll, PollSummary, UserStats, VoteRecord};
+
+// Account decoders for raw account data as returned by RPC.
+// The 8-byte discriminator is checked before deserializing.
+
+/// Decode any mvote account type
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_config(data: &[u8]) -> Result<Config> {
+    decode(data)
+}
+
+/// Legacy poll accounts must go through migrate_poll first
+pub fn decode_poll(data: &[u8]) -> Result<Poll> {
+    decode(data)
+}
+
+pub fn decode_poll_summary(data: &[u8]) -> Result<PollSummary> {
+    decode(data)
+}
+
+pub fn decode_user_stats(data: &[u8]) -> Result<UserStats> {
+    decode(data)
+}
+
+pub fn decode_vote_record(data: &[u8]) -> Result<VoteRecord> {
+    decode(data)
+}
@@ -0,0 +1,118 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use mvote::events::*;
+
+// Decoding of the events the program emits with `emit!`. Each event is logged
+// as "Program data: <base64>" holding its 8-byte discriminator followed by the
+// borsh-encoded struct.
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+macro_rules! mvote_events {
+    ($($name:ident),* $(,)?) => {
+        /// Any event emitted by the mvote program
+        pub enum MvoteEvent {
+            $($name($name),)*
+        }
+
+        impl MvoteEvent {
+            /// Event name as declared in the program
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(MvoteEvent::$name(_) => stringify!($name),)*
+                }
+            }
+        }
+
+        /// Decode one event from its raw log data, or `None` if the data is
+        /// not an mvote event
+        pub fn decode_event(data: &[u8]) -> Option<MvoteEvent> {
+            if data.len() < 8 {
+                return None;
+            }
+            let (discriminator, mut payload) = data.split_at(8);
+
+            $(
+                if discriminator == $name::DISCRIMINATOR {
+                    return $name::deserialize(&mut payload).ok().map(MvoteEvent::$name);
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+mvote_events!(
+    Initialized,
+    TokensPurchased,
+    TokensRedeemed,
+    PollCreated,
+    VoteCast,
+    VoteCommitted,
+    VoteChanged,
+    VoteRetracted,
+    VoteChangesSet,
+    PollClosed,
+    PollCancelled,
+    RankedTallied,
+    PollFinalized,
+    EscrowReclaimed,
+    RefundClaimed,
+    VoteRecordClosed,
+    PollArchived,
+    PollMigrated,
+    ConfigMigrated,
+    UserStatsMigrated,
+    VoteRecordMigrated,
+    ConfigUpdated,
+    AdminProposed,
+    AdminTransferred,
+    VaultWithdrawn,
+    PauseSet,
+);
+
+/// Events logged by the mvote program in a transaction's log messages.
+///
+/// Tracks the invocation stack so data logged by other programs, including
+/// programs mvote calls into, is ignored.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<MvoteEvent> {
+    let program_id = mvote::ID.to_string();
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let program = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => {
+                    stack.push(program == program_id);
+                    continue;
+                }
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        if stack.last() != Some(&true) {
+            continue;
+        }
+
+        if let Some(event) = log
+            .strip_prefix(PROGRAM_DATA)
+            .and_then(|data| BASE64.decode(data).ok())
+            .and_then(|data| decode_event(&data))
+        {
+            events.push(event);
+        }
+    }
+
+    events
+}
@@ -0,0 +1,364 @@
+use mvote::state::{Poll, PollSummary, VoteRecord, VotingStyle};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::format::{poll_outcome_name, vote_mode_name, voting_style_name};
+use crate::pda;
+
+// Full results of a poll for audits: the tally plus every vote record, as
+// CSV or canonical JSON. Only enabled with the `export` feature.
+//
+// Canonical JSON has object keys sorted, no whitespace and votes ordered by
+// voter address, so two exports of the same on-chain state are byte-identical.
+// `result_hash` is the hex sha256 of the canonical JSON of everything else in
+// the export. Nothing time-dependent (such as the live poll status) is
+// included, so the hash only changes when the results do.
+//
+// Archived polls are exported from their PollSummary, which keeps the tally
+// but not the question or option labels.
+
+/// Offset of `VoteRecord.poll_id`: discriminator and voter pubkey
+#[cfg(feature = "rpc")]
+const VOTE_RECORD_POLL_ID_OFFSET: usize = 8 + 32;
+
+pub struct PollExport {
+    pub poll: Poll,
+    /// Vote records of the poll, sorted by voter
+    pub votes: Vec<VoteRecord>,
+    /// Whether `poll` was rebuilt from the poll's summary
+    pub archived: bool,
+}
+
+impl PollExport {
+    /// Vote records belonging to other polls are dropped
+    pub fn new(poll: Poll, votes: impl IntoIterator<Item = VoteRecord>) -> Self {
+        let mut votes: Vec<VoteRecord> = votes
+            .into_iter()
+            .filter(|vote| vote.poll_id == poll.id)
+            .collect();
+        votes.sort_by_key(|vote| vote.voter.to_string());
+
+        Self {
+            poll,
+            votes,
+            archived: false,
+        }
+    }
+
+    /// Export of an archived poll. Labels are empty and settings the summary
+    /// does not keep are left at their defaults.
+    pub fn from_summary(
+        summary: &PollSummary,
+        votes: impl IntoIterator<Item = VoteRecord>,
+    ) -> Self {
+        let option_count = summary.option_count as usize;
+        let poll = Poll {
+            id: summary.poll_id,
+            creator: summary.creator,
+            question: String::new(),
+            options: vec![String::new(); option_count],
+            vote_counts: summary.vote_counts[..option_count].to_vec(),
+            vote_mode: summary.vote_mode,
+            voting_style: summary.voting_style,
+            start_time: 0,
+            end_time: summary.end_time,
+            total_votes: summary.total_votes,
+            total_tokens_spent: summary.total_tokens_spent,
+            status: summary.status,
+            allow_vote_changes: false,
+            is_tallied: false,
+            winning_option: summary.winning_option,
+            tally_rounds: Vec::new(),
+            is_secret: false,
+            reveal_end_time: 0,
+            committed_votes: 0,
+            revealed_votes: 0,
+            is_tie: summary.is_tie,
+            finalized_at: summary.finalized_at,
+            cancelled_at: summary.cancelled_at,
+            quorum: None,
+            pass_threshold_bps: 0,
+            outcome: summary.outcome,
+            outstanding_records: 0,
+            bump: 0,
+        };
+
+        Self {
+            archived: true,
+            ..Self::new(poll, votes)
+        }
+    }
+
+    /// Votes held by the exported records, comparable with `total_votes`.
+    /// Repeat votes add to one record's `vote_count`; secret ballots that
+    /// were never revealed are not counted.
+    pub fn counted_votes(&self) -> u64 {
+        self.votes
+            .iter()
+            .filter(|vote| !is_unrevealed(vote))
+            .map(|vote| vote.vote_count as u64)
+            .sum()
+    }
+
+    /// Whether every vote in `total_votes` still has its vote record. Records
+    /// closed after finalization or archiving make the export incomplete.
+    pub fn is_complete(&self) -> bool {
+        self.counted_votes() == self.poll.total_votes
+    }
+
+    /// Results without the hash
+    fn results(&self) -> Value {
+        let poll = &self.poll;
+        json!({
+            "poll_id": poll.id,
+            "address": pda::poll(poll.id).0.to_string(),
+            "creator": poll.creator.to_string(),
+            "question": poll.question,
+            "options": poll.options,
+            "vote_mode": vote_mode_name(poll.vote_mode),
+            "voting_style": voting_style_name(poll.voting_style),
+            "vote_counts": poll.vote_counts,
+            "total_votes": poll.total_votes,
+            "total_tokens_spent": poll.total_tokens_spent,
+            "winning_option": poll.winning_option,
+            "is_tie": poll.is_tie,
+            "outcome": poll_outcome_name(poll.outcome),
+            "archived": self.archived,
+            "vote_records": self.votes.len(),
+            "counted_votes": self.counted_votes(),
+            "is_complete": self.is_complete(),
+            "votes": self.votes.iter().map(|vote| json!({
+                "voter": vote.voter.to_string(),
+                "option_index": vote.option_index,
+                "option_mask": vote.option_mask,
+                "rankings": vote.rankings,
+                "option_tokens": vote.option_tokens,
+                "tokens_spent": vote.tokens_spent,
+                "vote_count": vote.vote_count,
+                "voted_at": vote.voted_at,
+                "is_revealed": vote.is_revealed,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Hex sha256 of the canonical JSON results
+    pub fn result_hash(&self) -> String {
+        Sha256::digest(canonical_json(&self.results()).as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut value = self.results();
+        value["result_hash"] = Value::String(self.result_hash());
+        value
+    }
+
+    /// Canonical JSON of the results and their hash
+    pub fn to_canonical_json(&self) -> String {
+        canonical_json(&self.to_json())
+    }
+
+    /// One `poll` row with the question and totals, one `option` row per
+    /// option with its vote count, then one `vote` row per vote record.
+    /// Unrevealed secret ballots have an empty selection.
+    pub fn to_csv(&self) -> csv::Result<String> {
+        let poll = &self.poll;
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "record",
+            "poll_id",
+            "option",
+            "label",
+            "voter",
+            "votes",
+            "tokens",
+            "voted_at",
+            "is_revealed",
+        ])?;
+
+        writer.write_record([
+            "poll",
+            &poll.id.to_string(),
+            "",
+            &poll.question,
+            "",
+            &poll.total_votes.to_string(),
+            &poll.total_tokens_spent.to_string(),
+            "",
+            "",
+        ])?;
+
+        for (index, option) in poll.options.iter().enumerate() {
+            let votes = poll.vote_counts.get(index).copied().unwrap_or_default();
+            writer.write_record([
+                "option",
+                &poll.id.to_string(),
+                &index.to_string(),
+                option,
+                "",
+                &votes.to_string(),
+                "",
+                "",
+                "",
+            ])?;
+        }
+
+        for vote in &self.votes {
+            let (selection, label) = self.selection(vote);
+            writer.write_record([
+                "vote",
+                &poll.id.to_string(),
+                &selection,
+                &label,
+                &vote.voter.to_string(),
+                &vote.vote_count.to_string(),
+                &vote.tokens_spent.to_string(),
+                &vote.voted_at.to_string(),
+                &vote.is_revealed.to_string(),
+            ])?;
+        }
+
+        let data = writer.into_inner().map_err(|err| err.into_error())?;
+        Ok(String::from_utf8(data).unwrap_or_default())
+    }
+
+    /// Chosen option indices and their labels. Approvals and options holding
+    /// tokens are joined with ";", rankings with ">", most preferred first.
+    fn selection(&self, vote: &VoteRecord) -> (String, String) {
+        if is_unrevealed(vote) {
+            return (String::new(), String::new());
+        }
+
+        let (indices, separator): (Vec<u8>, &str) = match self.poll.voting_style {
+            VotingStyle::Approval => (
+                (0..8)
+                    .filter(|bit| vote.option_mask & (1 << bit) != 0)
+                    .collect(),
+                ";",
+            ),
+            VotingStyle::RankedChoice => (vote.rankings.clone(), ">"),
+            VotingStyle::Binary | VotingStyle::MultipleChoice => {
+                // Changed and added votes can leave tokens on several options
+                let funded: Vec<u8> = (0..vote.option_tokens.len() as u8)
+                    .filter(|&index| vote.option_tokens[index as usize] > 0)
+                    .collect();
+                if funded.is_empty() {
+                    (vec![vote.option_index], "")
+                } else {
+                    (funded, ";")
+                }
+            }
+        };
+
+        let selection = indices
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(separator);
+        let label = indices
+            .iter()
+            .map(|&index| {
+                self.poll
+                    .options
+                    .get(index as usize)
+                    .map(String::as_str)
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(separator);
+        (selection, label)
+    }
+}
+
+/// Secret ballot whose commitment was never revealed
+fn is_unrevealed(vote: &VoteRecord) -> bool {
+    vote.commitment != [0; 32] && !vote.is_revealed
+}
+
+/// Fetch a poll and all of its vote records, falling back to the poll's
+/// summary once it is archived. `None` if neither account exists.
+#[cfg(feature = "rpc")]
+pub fn fetch(
+    rpc: &crate::rpc::RpcClient,
+    poll_id: u64,
+) -> crate::rpc::RpcResult<Option<PollExport>> {
+    use crate::accounts;
+    use crate::rpc::RpcError;
+
+    if let Some(data) = rpc.get_account_data(&pda::poll(poll_id).0)? {
+        let poll = accounts::decode_poll(&data)
+            .map_err(|err| RpcError::InvalidResponse(format!("poll {}: {}", poll_id, err)))?;
+        return Ok(Some(PollExport::new(poll, fetch_votes(rpc, poll_id)?)));
+    }
+
+    let Some(data) = rpc.get_account_data(&pda::poll_summary(poll_id).0)? else {
+        return Ok(None);
+    };
+    let summary = accounts::decode_poll_summary(&data)
+        .map_err(|err| RpcError::InvalidResponse(format!("poll summary {}: {}", poll_id, err)))?;
+    Ok(Some(PollExport::from_summary(
+        &summary,
+        fetch_votes(rpc, poll_id)?,
+    )))
+}
+
+#[cfg(feature = "rpc")]
+fn fetch_votes(
+    rpc: &crate::rpc::RpcClient,
+    poll_id: u64,
+) -> crate::rpc::RpcResult<Vec<VoteRecord>> {
+    use crate::accounts;
+    use crate::rpc::RpcError;
+
+    rpc.get_program_accounts_matching::<VoteRecord>(&[(
+        VOTE_RECORD_POLL_ID_OFFSET,
+        &poll_id.to_le_bytes(),
+    )])?
+    .iter()
+    .map(|(address, data)| {
+        accounts::decode_vote_record(data)
+            .map_err(|err| RpcError::InvalidResponse(format!("vote record {}: {}", address, err)))
+    })
+    .collect()
+}
+
+/// Compact JSON with object keys in sorted order, independent of how the
+/// value was built
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
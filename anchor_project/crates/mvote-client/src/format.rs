@@ -0,0 +1,86 @@
+use mvote::constants::TOKEN_DECIMALS;
+use mvote::state::{PollOutcome, PollStatus, VoteMode, VotingStyle};
+
+// Stable snake_case names for program enums, matching the IDL variant names,
+// and token amount conversion to and from whole mVote.
+
+pub fn vote_mode_name(vote_mode: VoteMode) -> &'static str {
+    match vote_mode {
+        VoteMode::FairVote => "fair_vote",
+        VoteMode::HoldingVote => "holding_vote",
+        VoteMode::EscrowVote => "escrow_vote",
+        VoteMode::Quadratic => "quadratic",
+    }
+}
+
+pub fn voting_style_name(voting_style: VotingStyle) -> &'static str {
+    match voting_style {
+        VotingStyle::Binary => "binary",
+        VotingStyle::MultipleChoice => "multiple_choice",
+        VotingStyle::RankedChoice => "ranked_choice",
+        VotingStyle::Approval => "approval",
+    }
+}
+
+pub fn poll_status_name(status: PollStatus) -> &'static str {
+    match status {
+        PollStatus::Scheduled => "scheduled",
+        PollStatus::Active => "active",
+        PollStatus::ClosedEarly => "closed_early",
+        PollStatus::Expired => "expired",
+        PollStatus::Finalized => "finalized",
+        PollStatus::Cancelled => "cancelled",
+    }
+}
+
+pub fn poll_outcome_name(outcome: PollOutcome) -> &'static str {
+    match outcome {
+        PollOutcome::Undecided => "undecided",
+        PollOutcome::Passed => "passed",
+        PollOutcome::Failed => "failed",
+        PollOutcome::QuorumNotMet => "quorum_not_met",
+    }
+}
+
+/// Token units as whole mVote, e.g. 1_500_000 -> "1.5"
+pub fn format_tokens(amount: u64) -> String {
+    let scale = 10u64.pow(TOKEN_DECIMALS as u32);
+    let fraction = amount % scale;
+    if fraction == 0 {
+        return (amount / scale).to_string();
+    }
+
+    let fraction = format!("{:0width$}", fraction, width = TOKEN_DECIMALS as usize);
+    format!("{}.{}", amount / scale, fraction.trim_end_matches('0'))
+}
+
+/// Whole mVote to token units, e.g. "1.5" -> 1_500_000. Rejects more than
+/// TOKEN_DECIMALS fractional digits.
+pub fn parse_tokens(amount: &str) -> Option<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if fraction.len() > TOKEN_DECIMALS as usize
+        || (whole.is_empty() && fraction.is_empty())
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let scale = 10u64.pow(TOKEN_DECIMALS as u32);
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>().ok()?
+    };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = TOKEN_DECIMALS as usize)
+            .parse::<u64>()
+            .ok()?
+    };
+
+    whole.checked_mul(scale)?.checked_add(fraction)
+}
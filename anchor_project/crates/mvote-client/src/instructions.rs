@@ -0,0 +1,199 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token;
+use mvote::instructions::PassRules;
+use mvote::state::{VoteMode, VotingStyle};
+
+use crate::pda;
+
+// Instruction builders for the core mvote flow. Every PDA is derived from the
+// instruction arguments, so callers only pass wallets and values.
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: mvote::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Create the config, token mint and SOL vault (one-time, admin pays)
+pub fn initialize(admin: &Pubkey) -> Instruction {
+    build(
+        mvote::accounts::Initialize {
+            admin: *admin,
+            config: pda::config().0,
+            token_mint: pda::mint().0,
+            sol_vault: pda::vault().0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            rent: sysvar::rent::ID,
+        },
+        mvote::instruction::Initialize {},
+    )
+}
+
+/// Create the buyer's mVote token account if it does not exist yet.
+/// purchase_tokens expects it to exist.
+pub fn create_token_account(payer: &Pubkey, wallet: &Pubkey) -> Instruction {
+    create_associated_token_account_idempotent(payer, wallet, &pda::mint().0, &token::ID)
+}
+
+/// Buy `amount` token units. `max_lamports` adds a slippage limit and
+/// `price_oracle` must match `Config.price_oracle` when one is configured.
+pub fn purchase_tokens(
+    buyer: &Pubkey,
+    amount: u64,
+    max_lamports: Option<u64>,
+    price_oracle: Option<Pubkey>,
+) -> Instruction {
+    let accounts = mvote::accounts::PurchaseTokens {
+        buyer: *buyer,
+        config: pda::config().0,
+        user_stats: pda::user_stats(buyer).0,
+        token_mint: pda::mint().0,
+        buyer_token_account: pda::token_account(buyer),
+        sol_vault: pda::vault().0,
+        price_oracle,
+        system_program: system_program::ID,
+        token_program: token::ID,
+    };
+
+    match max_lamports {
+        Some(max_lamports) => build(
+            accounts,
+            mvote::instruction::PurchaseTokensWithMaxCost {
+                amount,
+                max_lamports,
+            },
+        ),
+        None => build(accounts, mvote::instruction::PurchaseTokens { amount }),
+    }
+}
+
+/// Create poll `poll_id`, which must equal `Config.total_polls_created`
+#[allow(clippy::too_many_arguments)]
+pub fn create_poll(
+    creator: &Pubkey,
+    poll_id: u64,
+    question: String,
+    options: Vec<String>,
+    vote_mode: VoteMode,
+    voting_style: VotingStyle,
+    duration_minutes: u16,
+    start_time: Option<i64>,
+    pass_rules: Option<PassRules>,
+) -> Instruction {
+    let ranked_ballots =
+        (voting_style == VotingStyle::RankedChoice).then(|| pda::ranked_ballots(poll_id).0);
+
+    build(
+        mvote::accounts::CreatePoll {
+            creator: *creator,
+            config: pda::config().0,
+            user_stats: pda::user_stats(creator).0,
+            poll: pda::poll(poll_id).0,
+            token_mint: pda::mint().0,
+            creator_token_account: pda::token_account(creator),
+            ranked_ballots,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        mvote::instruction::CreatePoll {
+            question,
+            options,
+            vote_mode,
+            voting_style,
+            duration_minutes,
+            start_time,
+            pass_rules,
+        },
+    )
+}
+
+/// Vote on a poll. `vote_mode` must match the poll, since escrow polls need
+/// the escrow token account.
+pub fn vote(
+    voter: &Pubkey,
+    poll_id: u64,
+    vote_mode: VoteMode,
+    option_index: u8,
+    token_amount: u64,
+) -> Instruction {
+    let escrow_token_account = (vote_mode == VoteMode::EscrowVote).then(|| pda::escrow(poll_id).0);
+
+    build(
+        mvote::accounts::Vote {
+            voter: *voter,
+            config: pda::config().0,
+            user_stats: pda::user_stats(voter).0,
+            poll: pda::poll(poll_id).0,
+            vote_record: pda::vote_record(poll_id, voter).0,
+            token_mint: pda::mint().0,
+            voter_token_account: pda::token_account(voter),
+            escrow_token_account,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        mvote::instruction::Vote {
+            poll_id,
+            option_index,
+            token_amount,
+        },
+    )
+}
+
+/// Cast a ranked ballot, listing option indices from most to least preferred
+pub fn vote_ranked(voter: &Pubkey, poll_id: u64, rankings: Vec<u8>) -> Instruction {
+    build(
+        mvote::accounts::VoteRanked {
+            voter: *voter,
+            config: pda::config().0,
+            user_stats: pda::user_stats(voter).0,
+            poll: pda::poll(poll_id).0,
+            vote_record: pda::vote_record(poll_id, voter).0,
+            ranked_ballots: pda::ranked_ballots(poll_id).0,
+            token_mint: pda::mint().0,
+            voter_token_account: pda::token_account(voter),
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        mvote::instruction::VoteRanked { poll_id, rankings },
+    )
+}
+
+/// Approve the options set in `option_mask` (bit `i` approves option `i`)
+pub fn vote_approval(voter: &Pubkey, poll_id: u64, option_mask: u8) -> Instruction {
+    build(
+        mvote::accounts::VoteApproval {
+            voter: *voter,
+            config: pda::config().0,
+            user_stats: pda::user_stats(voter).0,
+            poll: pda::poll(poll_id).0,
+            vote_record: pda::vote_record(poll_id, voter).0,
+            token_mint: pda::mint().0,
+            voter_token_account: pda::token_account(voter),
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        mvote::instruction::VoteApproval {
+            poll_id,
+            option_mask,
+        },
+    )
+}
+
+/// Close an active poll early (creator only)
+pub fn close_poll(creator: &Pubkey, poll_id: u64) -> Instruction {
+    build(
+        mvote::accounts::ClosePoll {
+            creator: *creator,
+            poll: pda::poll(poll_id).0,
+        },
+        mvote::instruction::ClosePoll { poll_id },
+    )
+}
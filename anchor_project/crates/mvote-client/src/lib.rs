@@ -0,0 +1,23 @@
+// Rust client for the mvote program.
+//
+// Wraps the types generated by the program crate (built with `no-entrypoint`)
+// so off-chain services can derive PDAs, build instructions and decode
+// accounts without going through the TypeScript client.
+
+pub mod accounts;
+pub mod events;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod format;
+pub mod instructions;
+pub mod math;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+pub use mvote::state::{
+    Config, Poll, PollOutcome, PollStatus, UserStats, VoteMode, VoteRecord, VotingStyle,
+};
+pub use mvote::ID as PROGRAM_ID;
@@ -0,0 +1,20 @@
+use mvote::instructions::purchase_tokens;
+
+// Off-chain access to the program's pricing and daily-limit helpers in
+// instructions/purchase_tokens.rs.
+
+/// SOL cost in lamports for `token_amount` token units, or `None` on overflow
+/// or a zero rate
+pub fn calculate_sol_cost(
+    token_amount: u64,
+    sol_usd_rate: u64,
+    tokens_per_usd: u64,
+) -> Option<u64> {
+    purchase_tokens::calculate_sol_cost(token_amount, sol_usd_rate, tokens_per_usd).ok()
+}
+
+/// Whether `current_timestamp` falls on a later SGT (UTC+8) day than
+/// `last_timestamp`. A zero `last_timestamp` always starts a new day.
+pub fn is_new_day_sgt(last_timestamp: i64, current_timestamp: i64) -> bool {
+    purchase_tokens::is_new_day_sgt(last_timestamp, current_timestamp)
+}
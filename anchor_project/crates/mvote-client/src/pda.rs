@@ -0,0 +1,50 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use mvote::constants::*;
+
+// PDA derivation, using the same seeds as the program's account constraints.
+// Each helper returns the address together with its bump.
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &mvote::ID)
+}
+
+pub fn mint() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED], &mvote::ID)
+}
+
+pub fn vault() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED], &mvote::ID)
+}
+
+pub fn user_stats(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_SEED, wallet.as_ref()], &mvote::ID)
+}
+
+pub fn poll(poll_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POLL_SEED, poll_id.to_le_bytes().as_ref()], &mvote::ID)
+}
+
+pub fn vote_record(poll_id: u64, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.as_ref()],
+        &mvote::ID,
+    )
+}
+
+pub fn escrow(poll_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ESCROW_SEED, poll_id.to_le_bytes().as_ref()], &mvote::ID)
+}
+
+pub fn ranked_ballots(poll_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BALLOTS_SEED, poll_id.to_le_bytes().as_ref()], &mvote::ID)
+}
+
+pub fn poll_summary(poll_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUMMARY_SEED, poll_id.to_le_bytes().as_ref()], &mvote::ID)
+}
+
+/// Associated mVote token account of `wallet`
+pub fn token_account(wallet: &Pubkey) -> Pubkey {
+    get_associated_token_address(wallet, &mint().0)
+}
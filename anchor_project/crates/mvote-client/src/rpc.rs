@@ -0,0 +1,370 @@
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Discriminator;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+// Minimal blocking JSON-RPC client covering the calls the mvote tools need.
+// Only enabled with the `rpc` feature.
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug)]
+pub enum RpcError {
+    /// Connection or HTTP failure
+    Transport(String),
+    /// JSON-RPC error returned by the node
+    Rpc { code: i64, message: String },
+    /// Response did not have the expected shape
+    InvalidResponse(String),
+    /// Transaction was processed but failed
+    TransactionFailed { signature: String, error: Value },
+    /// Transaction was not confirmed before the timeout
+    ConfirmationTimeout(String),
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcError::Transport(message) => write!(f, "RPC transport error: {}", message),
+            RpcError::Rpc { code, message } => write!(f, "RPC error {}: {}", code, message),
+            RpcError::InvalidResponse(message) => write!(f, "invalid RPC response: {}", message),
+            RpcError::TransactionFailed { signature, error } => {
+                write!(f, "transaction {} failed: {}", signature, error)
+            }
+            RpcError::ConfirmationTimeout(signature) => {
+                write!(f, "transaction {} was not confirmed in time", signature)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+pub type RpcResult<T> = std::result::Result<T, RpcError>;
+
+/// Entry returned by getSignaturesForAddress
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+}
+
+/// Logs of a confirmed transaction
+pub struct TransactionLogs {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    pub logs: Vec<String>,
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Send a JSON-RPC request and decode its `result`
+    pub fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> RpcResult<T> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .map_err(|err| RpcError::Transport(err.to_string()))?
+            .into_json()
+            .map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+
+        serde_json::from_value(response["result"].clone())
+            .map_err(|err| RpcError::InvalidResponse(format!("{}: {}", method, err)))
+    }
+
+    /// Raw account data, or `None` if the account does not exist
+    pub fn get_account_data(&self, address: &Pubkey) -> RpcResult<Option<Vec<u8>>> {
+        let result: Value = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), {"encoding": "base64", "commitment": "confirmed"}]),
+        )?;
+
+        match &result["value"] {
+            Value::Null => Ok(None),
+            account => decode_account_data(account).map(Some),
+        }
+    }
+
+    /// Balance in lamports
+    pub fn get_balance(&self, address: &Pubkey) -> RpcResult<u64> {
+        let result: Value = self.call(
+            "getBalance",
+            json!([address.to_string(), {"commitment": "confirmed"}]),
+        )?;
+
+        result["value"]
+            .as_u64()
+            .ok_or_else(|| RpcError::InvalidResponse("getBalance: missing value".to_string()))
+    }
+
+    /// Every program account of type `T`, matched on its discriminator
+    pub fn get_program_accounts<T: Discriminator>(&self) -> RpcResult<Vec<(Pubkey, Vec<u8>)>> {
+        self.get_program_accounts_matching::<T>(&[])
+    }
+
+    /// Program accounts of type `T` whose data also holds `bytes` at each
+    /// `offset` (offsets include the discriminator)
+    pub fn get_program_accounts_matching<T: Discriminator>(
+        &self,
+        matches: &[(usize, &[u8])],
+    ) -> RpcResult<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = std::iter::once((0, T::DISCRIMINATOR))
+            .chain(matches.iter().copied())
+            .map(|(offset, bytes)| {
+                json!({
+                    "memcmp": {
+                        "offset": offset,
+                        "bytes": BASE64.encode(bytes),
+                        "encoding": "base64",
+                    }
+                })
+            })
+            .collect();
+
+        let result: Vec<Value> = self.call(
+            "getProgramAccounts",
+            json!([
+                mvote::ID.to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": "confirmed",
+                    "filters": filters,
+                }
+            ]),
+        )?;
+
+        result
+            .iter()
+            .map(|entry| {
+                let address = parse_pubkey(&entry["pubkey"])?;
+                let data = decode_account_data(&entry["account"])?;
+                Ok((address, data))
+            })
+            .collect()
+    }
+
+    /// Signatures of transactions that touched `address`, newest first.
+    /// Pages backwards from `before` and stops at `until` (both exclusive).
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> RpcResult<Vec<SignatureInfo>> {
+        let result: Vec<Value> = self.call(
+            "getSignaturesForAddress",
+            json!([
+                address.to_string(),
+                {
+                    "before": before,
+                    "until": until,
+                    "limit": limit,
+                    "commitment": "confirmed",
+                }
+            ]),
+        )?;
+
+        result
+            .iter()
+            .map(|entry| {
+                Ok(SignatureInfo {
+                    signature: entry["signature"]
+                        .as_str()
+                        .ok_or_else(|| {
+                            RpcError::InvalidResponse("signature entry without signature".into())
+                        })?
+                        .to_string(),
+                    slot: entry["slot"].as_u64().unwrap_or_default(),
+                    block_time: entry["blockTime"].as_i64(),
+                    failed: !entry["err"].is_null(),
+                })
+            })
+            .collect()
+    }
+
+    /// Log messages of a confirmed transaction, or `None` if it is unknown
+    pub fn get_transaction_logs(&self, signature: &str) -> RpcResult<Option<TransactionLogs>> {
+        let result: Value = self.call(
+            "getTransaction",
+            json!([
+                signature,
+                {
+                    "encoding": "json",
+                    "commitment": "confirmed",
+                    "maxSupportedTransactionVersion": 0,
+                }
+            ]),
+        )?;
+
+        if result.is_null() {
+            return Ok(None);
+        }
+
+        let logs = result["meta"]["logMessages"]
+            .as_array()
+            .map(|logs| {
+                logs.iter()
+                    .filter_map(|log| log.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Some(TransactionLogs {
+            slot: result["slot"].as_u64().unwrap_or_default(),
+            block_time: result["blockTime"].as_i64(),
+            failed: !result["meta"]["err"].is_null(),
+            logs,
+        }))
+    }
+
+    pub fn get_latest_blockhash(&self) -> RpcResult<Hash> {
+        let result: Value =
+            self.call("getLatestBlockhash", json!([{"commitment": "confirmed"}]))?;
+
+        result["value"]["blockhash"]
+            .as_str()
+            .and_then(|blockhash| Hash::from_str(blockhash).ok())
+            .ok_or_else(|| {
+                RpcError::InvalidResponse("getLatestBlockhash: missing blockhash".to_string())
+            })
+    }
+
+    /// Sign `instructions` with `payer` (and any extra `signers`), send them in one
+    /// transaction and wait until it is confirmed. Returns the signature.
+    pub fn send_and_confirm(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        signers: &[&Keypair],
+    ) -> RpcResult<String> {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.get_latest_blockhash()?,
+        );
+        let wire = bincode::serialize(&transaction)
+            .map_err(|err| RpcError::InvalidResponse(err.to_string()))?;
+
+        let signature: String = self.call(
+            "sendTransaction",
+            json!([BASE64.encode(wire), {"encoding": "base64", "preflightCommitment": "confirmed"}]),
+        )?;
+
+        self.confirm(&signature)?;
+
+        Ok(signature)
+    }
+
+    /// Wait until `signature` reaches confirmed commitment
+    pub fn confirm(&self, signature: &str) -> RpcResult<()> {
+        let started = Instant::now();
+
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result: Value = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(RpcError::TransactionFailed {
+                        signature: signature.to_string(),
+                        error: status["err"].clone(),
+                    });
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed") | Some("finalized")
+                ) {
+                    return Ok(());
+                }
+            }
+
+            thread::sleep(CONFIRM_POLL_INTERVAL);
+        }
+
+        Err(RpcError::ConfirmationTimeout(signature.to_string()))
+    }
+}
+
+/// Expand the Solana CLI cluster monikers into an RPC URL
+pub fn resolve_url(url: &str) -> String {
+    match url {
+        "localnet" | "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
+
+/// Current wall-clock time as a unix timestamp
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn parse_pubkey(value: &Value) -> RpcResult<Pubkey> {
+    value
+        .as_str()
+        .and_then(|address| Pubkey::from_str(address).ok())
+        .ok_or_else(|| RpcError::InvalidResponse(format!("invalid pubkey: {}", value)))
+}
+
+fn decode_account_data(account: &Value) -> RpcResult<Vec<u8>> {
+    account["data"][0]
+        .as_str()
+        .and_then(|data| BASE64.decode(data).ok())
+        .ok_or_else(|| RpcError::InvalidResponse("account data is not base64".to_string()))
+}
@@ -0,0 +1,11 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+
+// Helpers shared by the tests of the mvote crates. Only enabled with the
+// `test-utils` feature.
+
+/// Zero-initialised account of type `T`, decoded the way the program stores it
+pub fn zeroed<T: AccountDeserialize + Discriminator>() -> T {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.resize(4096, 0);
+    T::try_deserialize(&mut data.as_slice()).unwrap()
+}
@@ -0,0 +1,192 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize, Discriminator, InstructionData, Space};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use mvote::state::{Config, VoteMode};
+use mvote_client::{accounts, events, format, instructions, math, pda};
+
+#[test]
+fn pdas_match_program_seeds() {
+    let voter = Pubkey::new_unique();
+    let (poll, _) = pda::poll(7);
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"poll", 7u64.to_le_bytes().as_ref()], &mvote::ID);
+    assert_eq!(poll, expected);
+
+    let (vote_record, _) = pda::vote_record(7, &voter);
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"vote", 7u64.to_le_bytes().as_ref(), voter.as_ref()],
+        &mvote::ID,
+    );
+    assert_eq!(vote_record, expected);
+}
+
+#[test]
+fn sol_cost_at_known_rates() {
+    // 1 mVote = $1 at $150.00/SOL
+    assert_eq!(
+        math::calculate_sol_cost(1_000_000, 15_000, 1_000_000),
+        Some(6_666_666)
+    );
+    // 100 mVote = $100 at $1.00/SOL = 100 SOL
+    assert_eq!(
+        math::calculate_sol_cost(100_000_000, 100, 1_000_000),
+        Some(100_000_000_000)
+    );
+    // 10 mVote per dollar makes each token ten times cheaper
+    assert_eq!(
+        math::calculate_sol_cost(1_000_000, 15_000, 10_000_000),
+        Some(666_666)
+    );
+    assert_eq!(math::calculate_sol_cost(0, 15_000, 1_000_000), Some(0));
+}
+
+#[test]
+fn sol_cost_rounds_down() {
+    // 6.67 lamports
+    assert_eq!(math::calculate_sol_cost(1, 15_000, 1_000_000), Some(6));
+    // 0.67 lamports
+    assert_eq!(math::calculate_sol_cost(1, 15_000, 10_000_000), Some(0));
+}
+
+#[test]
+fn sol_cost_rejects_zero_rates_and_overflow() {
+    assert_eq!(math::calculate_sol_cost(1_000_000, 0, 1_000_000), None);
+    assert_eq!(math::calculate_sol_cost(1_000_000, 15_000, 0), None);
+
+    // Exactly u64::MAX lamports still fits, anything above does not
+    assert_eq!(
+        math::calculate_sol_cost(u64::MAX, 100_000_000_000, 1),
+        Some(u64::MAX)
+    );
+    assert_eq!(math::calculate_sol_cost(u64::MAX, 100, 1), None);
+}
+
+#[test]
+fn new_day_starts_at_sgt_midnight() {
+    // 2024-01-01 15:59:59 UTC is 23:59:59 SGT
+    let before_midnight = 1_704_124_799;
+
+    // A zero timestamp means no purchase yet
+    assert!(math::is_new_day_sgt(0, before_midnight));
+
+    assert!(math::is_new_day_sgt(before_midnight, before_midnight + 1));
+    assert!(!math::is_new_day_sgt(
+        before_midnight - 3600,
+        before_midnight
+    ));
+
+    // The next SGT day runs until 23:59:59 SGT on 2024-01-02
+    assert!(!math::is_new_day_sgt(
+        before_midnight + 1,
+        before_midnight + 86_400
+    ));
+    assert!(math::is_new_day_sgt(
+        before_midnight + 1,
+        before_midnight + 86_401
+    ));
+
+    // UTC midnight (08:00 SGT) is not a boundary
+    let utc_midnight = 1_704_153_600;
+    assert!(!math::is_new_day_sgt(utc_midnight - 1, utc_midnight));
+
+    // A clock that went backwards never starts a new day
+    assert!(!math::is_new_day_sgt(before_midnight + 1, before_midnight));
+}
+
+#[test]
+fn vote_builder_uses_escrow_only_for_escrow_polls() {
+    let voter = Pubkey::new_unique();
+
+    let ix = instructions::vote(&voter, 3, VoteMode::EscrowVote, 1, 5_000_000);
+    assert_eq!(&ix.data[..8], mvote::instruction::Vote::DISCRIMINATOR);
+    assert_eq!(ix.accounts[7].pubkey, pda::escrow(3).0);
+
+    // Omitted optional accounts are passed as the program id
+    let ix = instructions::vote(&voter, 3, VoteMode::FairVote, 1, 0);
+    assert_eq!(ix.accounts[7].pubkey, mvote::ID);
+}
+
+#[test]
+fn ranked_and_approval_builders_pass_ballot_arguments() {
+    let voter = Pubkey::new_unique();
+
+    let ix = instructions::vote_ranked(&voter, 3, vec![2, 0, 1]);
+    let data = mvote::instruction::VoteRanked {
+        poll_id: 3,
+        rankings: vec![2, 0, 1],
+    };
+    assert_eq!(ix.data, data.data());
+    assert_eq!(ix.accounts[5].pubkey, pda::ranked_ballots(3).0);
+
+    let ix = instructions::vote_approval(&voter, 3, 0b101);
+    let data = mvote::instruction::VoteApproval {
+        poll_id: 3,
+        option_mask: 0b101,
+    };
+    assert_eq!(ix.data, data.data());
+    assert_eq!(ix.accounts[4].pubkey, pda::vote_record(3, &voter).0);
+}
+
+#[test]
+fn decodes_config_account() {
+    let mut data = Config::DISCRIMINATOR.to_vec();
+    data.resize(8 + Config::INIT_SPACE, 0);
+
+    let mut config = accounts::decode_config(&data).unwrap();
+    config.vote_cost = 1_000_000;
+    config.total_polls_created = 12;
+    data.clear();
+    config.try_serialize(&mut data).unwrap();
+
+    let decoded = accounts::decode_config(&data).unwrap();
+    assert_eq!(decoded.vote_cost, 1_000_000);
+    assert_eq!(decoded.total_polls_created, 12);
+
+    // Wrong discriminator
+    assert!(accounts::decode_poll(&data).is_err());
+}
+
+#[test]
+fn token_amounts_round_trip() {
+    assert_eq!(format::parse_tokens("10"), Some(10_000_000));
+    assert_eq!(format::parse_tokens("2.5"), Some(2_500_000));
+    assert_eq!(format::parse_tokens(".000001"), Some(1));
+    assert_eq!(format::parse_tokens("1.0000001"), None);
+    assert_eq!(format::parse_tokens("-1"), None);
+    assert_eq!(format::parse_tokens("."), None);
+
+    assert_eq!(format::format_tokens(10_000_000), "10");
+    assert_eq!(format::format_tokens(2_500_000), "2.5");
+    assert_eq!(format::format_tokens(1), "0.000001");
+}
+
+#[test]
+fn parses_only_mvote_events_from_logs() {
+    let event = mvote::events::PauseSet {
+        admin: Pubkey::new_unique(),
+        paused: true,
+        timestamp: 1_700_000_000,
+    };
+    let mut data = mvote::events::PauseSet::DISCRIMINATOR.to_vec();
+    event.serialize(&mut data).unwrap();
+    let data = format!("Program data: {}", BASE64.encode(data));
+    let other = Pubkey::new_unique();
+
+    let logs = [
+        format!("Program {} invoke [1]", mvote::ID),
+        format!("Program {} invoke [2]", other),
+        data.clone(),
+        format!("Program {} success", other),
+        data.clone(),
+        format!("Program {} success", mvote::ID),
+        data,
+    ];
+
+    let events = events::parse_logs(&logs);
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        events::MvoteEvent::PauseSet(decoded) => assert!(decoded.paused),
+        other => panic!("unexpected event {}", other.name()),
+    }
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "export")]
+
+use anchor_lang::prelude::Pubkey;
+use mvote::state::{Poll, PollStatus, PollSummary, VoteMode, VoteRecord, VotingStyle};
+use mvote_client::export::{canonical_json, PollExport};
+use mvote_client::test_utils::zeroed;
+use serde_json::json;
+
+fn fixture() -> (Poll, Vec<VoteRecord>) {
+    let mut poll: Poll = zeroed();
+    poll.id = 4;
+    poll.question = "Lunch, \"today\"?".to_string();
+    poll.options = vec!["Pizza".to_string(), "Sushi".to_string()];
+    poll.vote_counts = vec![1, 1];
+    poll.total_votes = 2;
+    poll.total_tokens_spent = 3_000_000;
+
+    let votes = (0..3u8)
+        .map(|index| {
+            let mut vote: VoteRecord = zeroed();
+            vote.voter = Pubkey::new_unique();
+            // The last record belongs to another poll
+            vote.poll_id = if index < 2 { 4 } else { 5 };
+            vote.option_index = index % 2;
+            vote.tokens_spent = 1_000_000 + index as u64 * 1_000_000;
+            vote.vote_count = 1;
+            vote.voted_at = 1_700_000_000 + index as i64;
+            vote
+        })
+        .collect();
+
+    (poll, votes)
+}
+
+#[test]
+fn canonical_json_sorts_keys_and_drops_whitespace() {
+    let value = json!({ "b": [1, { "d": true, "c": null }], "a": "x y" });
+    assert_eq!(
+        canonical_json(&value),
+        r#"{"a":"x y","b":[1,{"c":null,"d":true}]}"#
+    );
+}
+
+#[test]
+fn result_hash_depends_only_on_results() {
+    let (poll, votes) = fixture();
+    let export = PollExport::new(poll.clone(), votes.clone());
+    assert_eq!(export.votes.len(), 2);
+
+    let reversed = PollExport::new(poll.clone(), votes.iter().rev().cloned());
+    assert_eq!(export.result_hash(), reversed.result_hash());
+    assert_eq!(export.to_canonical_json(), reversed.to_canonical_json());
+
+    let exported = export.to_json();
+    assert_eq!(exported["result_hash"], export.result_hash());
+    assert_eq!(exported["votes"].as_array().unwrap().len(), 2);
+    assert_eq!(exported["total_tokens_spent"], 3_000_000);
+
+    let mut changed = votes;
+    changed[0].tokens_spent += 1;
+    assert_ne!(
+        PollExport::new(poll, changed).result_hash(),
+        export.result_hash()
+    );
+}
+
+#[test]
+fn csv_has_poll_option_and_vote_rows() {
+    let (mut poll, mut votes) = fixture();
+    poll.voting_style = VotingStyle::Approval;
+    votes[0].option_mask = 0b11;
+
+    let csv = PollExport::new(poll, votes).to_csv().unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(
+        lines[0],
+        "record,poll_id,option,label,voter,votes,tokens,voted_at,is_revealed"
+    );
+    assert_eq!(lines[1], r#"poll,4,,"Lunch, ""today""?",,2,3000000,,"#);
+    assert_eq!(lines[2], "option,4,0,Pizza,,1,,,");
+    assert_eq!(lines[3], "option,4,1,Sushi,,1,,,");
+    assert_eq!(lines.len(), 6);
+    assert!(lines[4..]
+        .iter()
+        .any(|line| line.contains(",0;1,Pizza;Sushi,")));
+}
+
+#[test]
+fn csv_lists_funded_options_and_hides_unrevealed_ballots() {
+    let (poll, mut votes) = fixture();
+    votes[0].option_tokens = [1_000_000, 500_000, 0, 0];
+    votes[1].commitment = [7; 32];
+
+    let export = PollExport::new(poll, votes.clone());
+    let csv = export.to_csv().unwrap();
+
+    let row = |vote: &VoteRecord| {
+        csv.lines()
+            .find(|line| line.contains(&vote.voter.to_string()))
+            .unwrap()
+            .to_string()
+    };
+    assert!(row(&votes[0]).starts_with("vote,4,0;1,Pizza;Sushi,"));
+    assert!(row(&votes[0]).ends_with(",false"));
+    assert!(row(&votes[1]).starts_with("vote,4,,,"));
+
+    votes[1].is_revealed = true;
+    let revealed = PollExport::new(export.poll.clone(), votes.clone())
+        .to_csv()
+        .unwrap();
+    assert!(revealed.contains(&format!("vote,4,1,Sushi,{}", votes[1].voter)));
+    assert!(revealed.contains(",true\n"));
+}
+
+#[test]
+fn export_reports_missing_vote_records() {
+    let (poll, votes) = fixture();
+    let complete = PollExport::new(poll.clone(), votes.clone());
+    assert!(complete.is_complete());
+    assert_eq!(complete.to_json()["vote_records"], 2);
+
+    // One record was closed after the poll was finalized
+    let partial = PollExport::new(poll, votes.into_iter().skip(1));
+    assert!(!partial.is_complete());
+    assert_eq!(partial.to_json()["is_complete"], false);
+    assert_ne!(partial.result_hash(), complete.result_hash());
+}
+
+#[test]
+fn repeat_votes_on_one_record_count_toward_total_votes() {
+    let (mut poll, mut votes) = fixture();
+    // A HoldingVote voter voted three times; the other ballot is unrevealed
+    poll.vote_mode = VoteMode::HoldingVote;
+    poll.total_votes = 3;
+    votes[0].vote_count = 3;
+    votes[1].vote_count = 0;
+    votes[1].commitment = [7; 32];
+
+    let export = PollExport::new(poll.clone(), votes.clone());
+    assert_eq!(export.counted_votes(), 3);
+    assert!(export.is_complete());
+    assert_eq!(export.to_json()["counted_votes"], 3);
+
+    poll.total_votes = 4;
+    assert!(!PollExport::new(poll, votes).is_complete());
+}
+
+#[test]
+fn archived_poll_is_exported_from_its_summary() {
+    let (poll, votes) = fixture();
+    let mut summary: PollSummary = zeroed();
+    summary.poll_id = poll.id;
+    summary.creator = poll.creator;
+    summary.status = PollStatus::Finalized;
+    summary.option_count = 2;
+    summary.vote_counts = [1, 1, 0, 0];
+    summary.total_votes = 2;
+    summary.total_tokens_spent = 3_000_000;
+
+    let export = PollExport::from_summary(&summary, votes);
+    assert!(export.archived);
+    assert_eq!(export.votes.len(), 2);
+    assert_eq!(export.poll.vote_counts, [1, 1]);
+    assert!(export.is_complete());
+
+    let exported = export.to_json();
+    assert_eq!(exported["archived"], true);
+    assert_eq!(exported["options"].as_array().unwrap().len(), 2);
+    assert_eq!(export.to_csv().unwrap().lines().count(), 6);
+}
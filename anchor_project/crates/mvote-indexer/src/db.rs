@@ -0,0 +1,761 @@
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use mvote::state::{Poll, PollOutcome, PollStatus, PollSummary, UserStats, VoteRecord};
+use mvote_client::events::MvoteEvent;
+use mvote_client::format::{
+    poll_outcome_name, poll_status_name, vote_mode_name, voting_style_name,
+};
+use rusqlite::{params, Connection, OptionalExtension, Result, Row, Transaction};
+use serde::Serialize;
+
+// SQLite store for indexed mvote data.
+//
+// polls, votes and users mirror program accounts and are rewritten on every
+// account sync. Accounts that disappear (closed vote records, archived polls)
+// are kept with is_closed = 1; retracted votes also get is_retracted = 1.
+// Archived polls are refreshed from their PollSummary.
+//
+// Poll and vote events are applied to the same rows, so replaying history
+// after a reset rebuilds accounts that no longer exist. Each transaction is
+// applied once, recorded in transactions. purchases come from
+// TokensPurchased events and are append-only, keyed by transaction signature
+// and event index.
+//
+// Token amounts are raw token units. SQLite integers are signed, so u64
+// values are stored as i64.
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS polls (
+    id                 INTEGER PRIMARY KEY,
+    address            TEXT NOT NULL,
+    creator            TEXT NOT NULL,
+    question           TEXT NOT NULL,
+    options            TEXT NOT NULL,
+    vote_counts        TEXT NOT NULL,
+    vote_mode          TEXT NOT NULL,
+    voting_style       TEXT NOT NULL,
+    status             TEXT NOT NULL,
+    start_time         INTEGER NOT NULL,
+    end_time           INTEGER NOT NULL,
+    total_votes        INTEGER NOT NULL,
+    total_tokens_spent INTEGER NOT NULL,
+    is_secret          INTEGER NOT NULL,
+    winning_option     INTEGER,
+    is_tie             INTEGER NOT NULL,
+    outcome            TEXT NOT NULL,
+    finalized_at       INTEGER NOT NULL,
+    cancelled_at       INTEGER NOT NULL,
+    is_closed          INTEGER NOT NULL DEFAULT 0,
+    updated_at         INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS polls_creator ON polls (creator);
+
+CREATE TABLE IF NOT EXISTS votes (
+    address          TEXT PRIMARY KEY,
+    poll_id          INTEGER NOT NULL,
+    voter            TEXT NOT NULL,
+    option_index     INTEGER NOT NULL,
+    option_mask      INTEGER NOT NULL,
+    rankings         TEXT NOT NULL,
+    tokens_spent     INTEGER NOT NULL,
+    vote_count       INTEGER NOT NULL,
+    voted_at         INTEGER NOT NULL,
+    tokens_reclaimed INTEGER NOT NULL,
+    is_revealed      INTEGER NOT NULL,
+    is_closed        INTEGER NOT NULL DEFAULT 0,
+    is_retracted     INTEGER NOT NULL DEFAULT 0,
+    updated_at       INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS votes_poll ON votes (poll_id);
+CREATE INDEX IF NOT EXISTS votes_voter ON votes (voter);
+
+CREATE TABLE IF NOT EXISTS users (
+    wallet                 TEXT PRIMARY KEY,
+    total_tokens_purchased INTEGER NOT NULL,
+    total_tokens_redeemed  INTEGER NOT NULL,
+    polls_created          INTEGER NOT NULL,
+    votes_cast             INTEGER NOT NULL,
+    updated_at             INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS purchases (
+    signature      TEXT NOT NULL,
+    event_index    INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    buyer          TEXT NOT NULL,
+    amount         INTEGER NOT NULL,
+    sol_cost       INTEGER NOT NULL,
+    sol_usd_rate   INTEGER NOT NULL,
+    purchased_at   INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS purchases_buyer ON purchases (buyer);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot      INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    key   TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+const TABLES: [&str; 6] = [
+    "polls",
+    "votes",
+    "users",
+    "purchases",
+    "transactions",
+    "sync_state",
+];
+
+/// Newest transaction signature whose events have been indexed
+const LAST_SIGNATURE: &str = "last_signature";
+
+#[derive(Debug, Serialize)]
+pub struct PollRow {
+    pub id: u64,
+    pub address: String,
+    pub creator: String,
+    pub question: String,
+    pub options: Vec<String>,
+    pub vote_counts: Vec<u64>,
+    pub vote_mode: String,
+    pub voting_style: String,
+    pub status: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_votes: u64,
+    pub total_tokens_spent: u64,
+    pub is_secret: bool,
+    pub winning_option: Option<u8>,
+    pub is_tie: bool,
+    pub outcome: String,
+    pub finalized_at: i64,
+    pub cancelled_at: i64,
+    pub is_closed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VoteRow {
+    pub address: String,
+    pub poll_id: u64,
+    pub voter: String,
+    pub option_index: u8,
+    pub option_mask: u8,
+    pub rankings: Vec<u8>,
+    pub tokens_spent: u64,
+    pub vote_count: u32,
+    pub voted_at: i64,
+    pub tokens_reclaimed: bool,
+    pub is_revealed: bool,
+    pub is_closed: bool,
+    pub is_retracted: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserRow {
+    pub wallet: String,
+    pub total_tokens_purchased: u64,
+    pub total_tokens_redeemed: u64,
+    pub polls_created: u64,
+    pub votes_cast: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurchaseRow {
+    pub signature: String,
+    pub event_index: u32,
+    pub slot: u64,
+    pub buyer: String,
+    pub amount: u64,
+    pub sol_cost: u64,
+    pub sol_usd_rate: u64,
+    pub purchased_at: i64,
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Drop all indexed data so the next sync starts from scratch
+    pub fn reset(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        for table in TABLES {
+            tx.execute_batch(&format!("DROP TABLE IF EXISTS {}", table))?;
+        }
+        tx.execute_batch(SCHEMA)?;
+        tx.commit()
+    }
+
+    /// Replace the account-backed tables with a fresh snapshot. Rows missing
+    /// from the snapshot are marked closed rather than deleted; archived polls
+    /// take their results from `summaries`.
+    pub fn apply_account_snapshot(
+        &mut self,
+        polls: &[Poll],
+        summaries: &[PollSummary],
+        votes: &[(Pubkey, VoteRecord)],
+        users: &[UserStats],
+        synced_at: i64,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("UPDATE polls SET is_closed = 1", [])?;
+        tx.execute("UPDATE votes SET is_closed = 1", [])?;
+
+        for poll in polls {
+            tx.execute(
+                "INSERT OR REPLACE INTO polls (
+                    id, address, creator, question, options, vote_counts, vote_mode,
+                    voting_style, status, start_time, end_time, total_votes,
+                    total_tokens_spent, is_secret, winning_option, is_tie, outcome,
+                    finalized_at, cancelled_at, is_closed, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+                    ?15, ?16, ?17, ?18, ?19, 0, ?20)",
+                params![
+                    poll.id as i64,
+                    mvote_client::pda::poll(poll.id).0.to_string(),
+                    poll.creator.to_string(),
+                    poll.question,
+                    to_json(&poll.options),
+                    to_json(&poll.vote_counts),
+                    vote_mode_name(poll.vote_mode),
+                    voting_style_name(poll.voting_style),
+                    poll_status_name(poll.current_status(synced_at)),
+                    poll.start_time,
+                    poll.end_time,
+                    poll.total_votes as i64,
+                    poll.total_tokens_spent as i64,
+                    poll.is_secret,
+                    poll.winning_option,
+                    poll.is_tie,
+                    poll_outcome_name(poll.outcome),
+                    poll.finalized_at,
+                    poll.cancelled_at,
+                    synced_at,
+                ],
+            )?;
+        }
+
+        // Summaries keep no question or labels; an archived poll whose
+        // PollCreated was never indexed gets empty ones
+        for summary in summaries {
+            let option_count = summary.option_count as usize;
+            tx.execute(
+                "INSERT INTO polls (
+                    id, address, creator, question, options, vote_counts, vote_mode,
+                    voting_style, status, start_time, end_time, total_votes,
+                    total_tokens_spent, is_secret, winning_option, is_tie, outcome,
+                    finalized_at, cancelled_at, is_closed, updated_at
+                ) VALUES (?1, ?2, ?3, '', ?4, ?5, ?6, ?7, ?8, 0, ?9, ?10, ?11, 0, ?12,
+                    ?13, ?14, ?15, ?16, 1, ?17)
+                ON CONFLICT (id) DO UPDATE SET
+                    vote_counts = excluded.vote_counts,
+                    status = excluded.status,
+                    end_time = excluded.end_time,
+                    total_votes = excluded.total_votes,
+                    total_tokens_spent = excluded.total_tokens_spent,
+                    winning_option = excluded.winning_option,
+                    is_tie = excluded.is_tie,
+                    outcome = excluded.outcome,
+                    finalized_at = excluded.finalized_at,
+                    cancelled_at = excluded.cancelled_at,
+                    is_closed = 1,
+                    updated_at = excluded.updated_at",
+                params![
+                    summary.poll_id as i64,
+                    mvote_client::pda::poll(summary.poll_id).0.to_string(),
+                    summary.creator.to_string(),
+                    to_json(&vec![String::new(); option_count]),
+                    to_json(&summary.vote_counts[..option_count]),
+                    vote_mode_name(summary.vote_mode),
+                    voting_style_name(summary.voting_style),
+                    poll_status_name(summary.status),
+                    summary.end_time,
+                    summary.total_votes as i64,
+                    summary.total_tokens_spent as i64,
+                    summary.winning_option,
+                    summary.is_tie,
+                    poll_outcome_name(summary.outcome),
+                    summary.finalized_at,
+                    summary.cancelled_at,
+                    synced_at,
+                ],
+            )?;
+        }
+
+        for (address, vote) in votes {
+            tx.execute(
+                "INSERT OR REPLACE INTO votes (
+                    address, poll_id, voter, option_index, option_mask, rankings,
+                    tokens_spent, vote_count, voted_at, tokens_reclaimed, is_revealed,
+                    is_closed, is_retracted, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0, ?12)",
+                params![
+                    address.to_string(),
+                    vote.poll_id as i64,
+                    vote.voter.to_string(),
+                    vote.option_index,
+                    vote.option_mask,
+                    to_json(&vote.rankings),
+                    vote.tokens_spent as i64,
+                    vote.vote_count,
+                    vote.voted_at,
+                    vote.tokens_reclaimed,
+                    vote.is_revealed,
+                    synced_at,
+                ],
+            )?;
+        }
+
+        for user in users {
+            tx.execute(
+                "INSERT OR REPLACE INTO users (
+                    wallet, total_tokens_purchased, total_tokens_redeemed, polls_created,
+                    votes_cast, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    user.wallet.to_string(),
+                    user.total_tokens_purchased as i64,
+                    user.total_tokens_redeemed as i64,
+                    user.polls_created as i64,
+                    user.votes_cast as i64,
+                    synced_at,
+                ],
+            )?;
+        }
+
+        tx.commit()
+    }
+
+    /// Apply the events of one transaction and advance the signature cursor
+    /// in the same database transaction. A transaction that was already
+    /// applied only moves the cursor.
+    pub fn apply_transaction(
+        &mut self,
+        signature: &str,
+        slot: u64,
+        events: &[MvoteEvent],
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        let is_new = tx.execute(
+            "INSERT OR IGNORE INTO transactions (signature, slot) VALUES (?1, ?2)",
+            params![signature, slot as i64],
+        )? > 0;
+        if is_new {
+            for (event_index, event) in events.iter().enumerate() {
+                apply_event(&tx, signature, slot, event_index as u32, event)?;
+            }
+        }
+
+        tx.execute(
+            "INSERT OR REPLACE INTO sync_state (key, value) VALUES (?1, ?2)",
+            params![LAST_SIGNATURE, signature],
+        )?;
+
+        tx.commit()
+    }
+
+    pub fn last_signature(&self) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT value FROM sync_state WHERE key = ?1",
+                [LAST_SIGNATURE],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    pub fn polls(&self) -> Result<Vec<PollRow>> {
+        let mut stmt = self.conn.prepare("SELECT * FROM polls ORDER BY id")?;
+        let rows = stmt.query_map([], poll_row)?;
+        rows.collect()
+    }
+
+    pub fn polls_by_creator(&self, creator: &Pubkey) -> Result<Vec<PollRow>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM polls WHERE creator = ?1 ORDER BY id")?;
+        let rows = stmt.query_map([creator.to_string()], poll_row)?;
+        rows.collect()
+    }
+
+    pub fn poll(&self, poll_id: u64) -> Result<Option<PollRow>> {
+        self.conn
+            .query_row(
+                "SELECT * FROM polls WHERE id = ?1",
+                [poll_id as i64],
+                poll_row,
+            )
+            .optional()
+    }
+
+    pub fn votes_for_poll(&self, poll_id: u64) -> Result<Vec<VoteRow>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM votes WHERE poll_id = ?1 ORDER BY voted_at, voter")?;
+        let rows = stmt.query_map([poll_id as i64], vote_row)?;
+        rows.collect()
+    }
+
+    pub fn votes_by_voter(&self, voter: &Pubkey) -> Result<Vec<VoteRow>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM votes WHERE voter = ?1 ORDER BY poll_id")?;
+        let rows = stmt.query_map([voter.to_string()], vote_row)?;
+        rows.collect()
+    }
+
+    pub fn users(&self) -> Result<Vec<UserRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT wallet, total_tokens_purchased, total_tokens_redeemed, polls_created,
+                votes_cast FROM users ORDER BY total_tokens_purchased DESC, wallet",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(UserRow {
+                wallet: row.get(0)?,
+                total_tokens_purchased: row.get::<_, i64>(1)? as u64,
+                total_tokens_redeemed: row.get::<_, i64>(2)? as u64,
+                polls_created: row.get::<_, i64>(3)? as u64,
+                votes_cast: row.get::<_, i64>(4)? as u64,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Purchases, newest first, optionally for a single buyer
+    pub fn purchases(&self, buyer: Option<&Pubkey>) -> Result<Vec<PurchaseRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signature, event_index, slot, buyer, amount, sol_cost, sol_usd_rate,
+                purchased_at FROM purchases
+             WHERE ?1 IS NULL OR buyer = ?1
+             ORDER BY slot DESC, signature, event_index",
+        )?;
+        let rows = stmt.query_map([buyer.map(|buyer| buyer.to_string())], |row| {
+            Ok(PurchaseRow {
+                signature: row.get(0)?,
+                event_index: row.get(1)?,
+                slot: row.get::<_, i64>(2)? as u64,
+                buyer: row.get(3)?,
+                amount: row.get::<_, i64>(4)? as u64,
+                sol_cost: row.get::<_, i64>(5)? as u64,
+                sol_usd_rate: row.get::<_, i64>(6)? as u64,
+                purchased_at: row.get(7)?,
+            })
+        })?;
+        rows.collect()
+    }
+}
+
+/// Apply one event to the rows it affects. The account snapshot that follows
+/// each sync overwrites these rows for accounts that still exist.
+fn apply_event(
+    tx: &Transaction,
+    signature: &str,
+    slot: u64,
+    event_index: u32,
+    event: &MvoteEvent,
+) -> Result<()> {
+    match event {
+        MvoteEvent::TokensPurchased(purchase) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO purchases (
+                    signature, event_index, slot, buyer, amount, sol_cost, sol_usd_rate,
+                    purchased_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    signature,
+                    event_index,
+                    slot as i64,
+                    purchase.buyer.to_string(),
+                    purchase.amount as i64,
+                    purchase.sol_cost as i64,
+                    purchase.sol_usd_rate as i64,
+                    purchase.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::PollCreated(created) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO polls (
+                    id, address, creator, question, options, vote_counts, vote_mode,
+                    voting_style, status, start_time, end_time, total_votes,
+                    total_tokens_spent, is_secret, winning_option, is_tie, outcome,
+                    finalized_at, cancelled_at, is_closed, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0, ?12, NULL, 0,
+                    ?13, 0, 0, 0, ?14)",
+                params![
+                    created.poll_id as i64,
+                    created.poll.to_string(),
+                    created.creator.to_string(),
+                    created.question,
+                    to_json(&created.options),
+                    to_json(&vec![0u64; created.options.len()]),
+                    vote_mode_name(created.vote_mode),
+                    voting_style_name(created.voting_style),
+                    poll_status_name(created.status),
+                    created.start_time,
+                    created.end_time,
+                    created.is_secret,
+                    poll_outcome_name(PollOutcome::Undecided),
+                    created.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::PollClosed(closed) => {
+            tx.execute(
+                "UPDATE polls SET status = ?2, vote_counts = ?3, total_votes = ?4,
+                    total_tokens_spent = ?5, updated_at = ?6
+                 WHERE id = ?1",
+                params![
+                    closed.poll_id as i64,
+                    poll_status_name(PollStatus::ClosedEarly),
+                    to_json(&closed.vote_counts),
+                    closed.total_votes as i64,
+                    closed.total_tokens_spent as i64,
+                    closed.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::PollCancelled(cancelled) => {
+            tx.execute(
+                "UPDATE polls SET status = ?2, total_tokens_spent = ?3, cancelled_at = ?4,
+                    updated_at = ?4
+                 WHERE id = ?1",
+                params![
+                    cancelled.poll_id as i64,
+                    poll_status_name(PollStatus::Cancelled),
+                    cancelled.total_tokens_spent as i64,
+                    cancelled.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::RankedTallied(tallied) => {
+            tx.execute(
+                "UPDATE polls SET winning_option = ?2, updated_at = ?3 WHERE id = ?1",
+                params![
+                    tallied.poll_id as i64,
+                    tallied.winning_option,
+                    tallied.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::PollFinalized(finalized) => {
+            tx.execute(
+                "UPDATE polls SET status = ?2, winning_option = ?3, is_tie = ?4,
+                    outcome = ?5, vote_counts = ?6, total_votes = ?7,
+                    total_tokens_spent = ?8, finalized_at = ?9, updated_at = ?9
+                 WHERE id = ?1",
+                params![
+                    finalized.poll_id as i64,
+                    poll_status_name(PollStatus::Finalized),
+                    finalized.winning_option,
+                    finalized.is_tie,
+                    poll_outcome_name(finalized.outcome),
+                    to_json(&finalized.vote_counts),
+                    finalized.total_votes as i64,
+                    finalized.total_tokens_spent as i64,
+                    finalized.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::PollArchived(archived) => {
+            tx.execute(
+                "UPDATE polls SET is_closed = 1, updated_at = ?2 WHERE id = ?1",
+                params![archived.poll_id as i64, archived.timestamp],
+            )?;
+        }
+        MvoteEvent::VoteCommitted(committed) => {
+            // Counted once revealed; a retracted record can be reused
+            tx.execute(
+                "INSERT INTO votes (
+                    address, poll_id, voter, option_index, option_mask, rankings,
+                    tokens_spent, vote_count, voted_at, tokens_reclaimed, is_revealed,
+                    is_closed, is_retracted, updated_at
+                ) VALUES (?1, ?2, ?3, 0, 0, '[]', ?4, 0, ?5, 0, 0, 0, 0, ?5)
+                ON CONFLICT (address) DO UPDATE SET
+                    option_index = 0,
+                    option_mask = 0,
+                    rankings = '[]',
+                    tokens_spent = excluded.tokens_spent,
+                    vote_count = 0,
+                    voted_at = excluded.voted_at,
+                    tokens_reclaimed = 0,
+                    is_revealed = 0,
+                    is_closed = 0,
+                    is_retracted = 0,
+                    updated_at = excluded.updated_at
+                WHERE votes.is_retracted = 1",
+                params![
+                    committed.vote_record.to_string(),
+                    committed.poll_id as i64,
+                    committed.voter.to_string(),
+                    committed.tokens_spent as i64,
+                    committed.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::VoteCast(cast) => {
+            // Repeat votes add to the record and a reveal counts a committed
+            // one. A vote cast after a retraction starts the record afresh.
+            tx.execute(
+                "INSERT INTO votes (
+                    address, poll_id, voter, option_index, option_mask, rankings,
+                    tokens_spent, vote_count, voted_at, tokens_reclaimed, is_revealed,
+                    is_closed, is_retracted, updated_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, '[]', ?6, 1, ?7, 0, 0, 0, 0, ?7)
+                ON CONFLICT (address) DO UPDATE SET
+                    option_index = excluded.option_index,
+                    option_mask = CASE WHEN votes.is_retracted = 1
+                        THEN excluded.option_mask
+                        ELSE votes.option_mask | excluded.option_mask END,
+                    rankings = CASE WHEN votes.is_retracted = 1
+                        THEN excluded.rankings ELSE votes.rankings END,
+                    tokens_spent = CASE WHEN votes.is_retracted = 1
+                        THEN excluded.tokens_spent
+                        ELSE votes.tokens_spent + excluded.tokens_spent END,
+                    vote_count = CASE WHEN votes.is_retracted = 1
+                        THEN 1 ELSE votes.vote_count + 1 END,
+                    voted_at = excluded.voted_at,
+                    tokens_reclaimed = CASE WHEN votes.is_retracted = 1
+                        THEN 0 ELSE votes.tokens_reclaimed END,
+                    is_revealed = CASE WHEN votes.is_retracted = 1 THEN 0
+                        WHEN votes.vote_count = 0 THEN 1 ELSE votes.is_revealed END,
+                    is_closed = 0,
+                    is_retracted = 0,
+                    updated_at = excluded.updated_at",
+                params![
+                    cast.vote_record.to_string(),
+                    cast.poll_id as i64,
+                    cast.voter.to_string(),
+                    cast.option_index,
+                    cast.option_mask,
+                    cast.tokens_spent as i64,
+                    cast.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::VoteChanged(changed) => {
+            tx.execute(
+                "UPDATE votes SET option_index = ?3, option_mask = ?4, updated_at = ?5
+                 WHERE poll_id = ?1 AND voter = ?2 AND is_retracted = 0",
+                params![
+                    changed.poll_id as i64,
+                    changed.voter.to_string(),
+                    changed.new_option_index,
+                    1u8 << changed.new_option_index,
+                    changed.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::VoteRetracted(retracted) => {
+            tx.execute(
+                "UPDATE votes SET is_closed = 1, is_retracted = 1, updated_at = ?3
+                 WHERE poll_id = ?1 AND voter = ?2",
+                params![
+                    retracted.poll_id as i64,
+                    retracted.voter.to_string(),
+                    retracted.timestamp,
+                ],
+            )?;
+        }
+        MvoteEvent::EscrowReclaimed(reclaimed) => {
+            mark_tokens_reclaimed(tx, reclaimed.poll_id, &reclaimed.voter, reclaimed.timestamp)?;
+        }
+        MvoteEvent::RefundClaimed(refunded) => {
+            mark_tokens_reclaimed(tx, refunded.poll_id, &refunded.voter, refunded.timestamp)?;
+        }
+        MvoteEvent::VoteRecordClosed(closed) => {
+            tx.execute(
+                "UPDATE votes SET is_closed = 1, updated_at = ?2 WHERE address = ?1",
+                params![closed.vote_record.to_string(), closed.timestamp],
+            )?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn mark_tokens_reclaimed(
+    tx: &Transaction,
+    poll_id: u64,
+    voter: &Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    tx.execute(
+        "UPDATE votes SET tokens_reclaimed = 1, updated_at = ?3
+         WHERE poll_id = ?1 AND voter = ?2 AND is_retracted = 0",
+        params![poll_id as i64, voter.to_string(), timestamp],
+    )?;
+    Ok(())
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn from_json<T: serde::de::DeserializeOwned + Default>(value: String) -> T {
+    serde_json::from_str(&value).unwrap_or_default()
+}
+
+fn poll_row(row: &Row) -> Result<PollRow> {
+    Ok(PollRow {
+        id: row.get::<_, i64>("id")? as u64,
+        address: row.get("address")?,
+        creator: row.get("creator")?,
+        question: row.get("question")?,
+        options: from_json(row.get("options")?),
+        vote_counts: from_json(row.get("vote_counts")?),
+        vote_mode: row.get("vote_mode")?,
+        voting_style: row.get("voting_style")?,
+        status: row.get("status")?,
+        start_time: row.get("start_time")?,
+        end_time: row.get("end_time")?,
+        total_votes: row.get::<_, i64>("total_votes")? as u64,
+        total_tokens_spent: row.get::<_, i64>("total_tokens_spent")? as u64,
+        is_secret: row.get("is_secret")?,
+        winning_option: row.get("winning_option")?,
+        is_tie: row.get("is_tie")?,
+        outcome: row.get("outcome")?,
+        finalized_at: row.get("finalized_at")?,
+        cancelled_at: row.get("cancelled_at")?,
+        is_closed: row.get("is_closed")?,
+    })
+}
+
+fn vote_row(row: &Row) -> Result<VoteRow> {
+    Ok(VoteRow {
+        address: row.get("address")?,
+        poll_id: row.get::<_, i64>("poll_id")? as u64,
+        voter: row.get("voter")?,
+        option_index: row.get("option_index")?,
+        option_mask: row.get("option_mask")?,
+        rankings: from_json(row.get("rankings")?),
+        tokens_spent: row.get::<_, i64>("tokens_spent")? as u64,
+        vote_count: row.get("vote_count")?,
+        voted_at: row.get("voted_at")?,
+        tokens_reclaimed: row.get("tokens_reclaimed")?,
+        is_revealed: row.get("is_revealed")?,
+        is_closed: row.get("is_closed")?,
+        is_retracted: row.get("is_retracted")?,
+    })
+}
@@ -0,0 +1,11 @@
+// Off-chain indexer for the mvote program.
+//
+// Snapshots program accounts (polls, vote records, user stats) and replays
+// transaction logs for events into a SQLite database, so polls can be listed
+// and voters queried without walking poll IDs over RPC.
+
+pub mod db;
+pub mod sync;
+
+pub use db::Database;
+pub use sync::Indexer;
@@ -0,0 +1,163 @@
+// `mvote-indexer` binary.
+//
+// `sync` brings the SQLite database up to date with the chain (optionally in
+// a loop); the other subcommands query the database and print JSON.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use mvote_client::rpc::{resolve_url, RpcClient};
+use mvote_indexer::{Database, Indexer};
+use serde_json::{json, Value};
+
+#[derive(Parser)]
+#[command(
+    name = "mvote-indexer",
+    version,
+    about = "Index mvote polls, votes and purchases into SQLite"
+)]
+struct Cli {
+    /// RPC URL or moniker (localnet, devnet, mainnet-beta)
+    #[arg(short = 'u', long, global = true, default_value = "localnet")]
+    url: String,
+
+    /// SQLite database file
+    #[arg(long, global = true, default_value = "mvote-index.sqlite")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Bring the database up to date with the chain
+    Sync {
+        /// Discard all indexed data and re-sync from scratch
+        #[arg(long)]
+        reset: bool,
+
+        /// Keep syncing, waiting this many seconds between runs
+        #[arg(long)]
+        watch: Option<u64>,
+    },
+
+    /// List indexed polls
+    Polls {
+        /// Only polls created by this wallet
+        #[arg(long)]
+        creator: Option<String>,
+    },
+
+    /// Show one indexed poll with its votes
+    Poll { poll_id: u64 },
+
+    /// List votes for a poll or by a voter
+    Votes {
+        #[arg(long, conflicts_with = "voter", required_unless_present = "voter")]
+        poll: Option<u64>,
+
+        #[arg(long)]
+        voter: Option<String>,
+    },
+
+    /// List token purchases, newest first
+    Purchases {
+        #[arg(long)]
+        buyer: Option<String>,
+    },
+
+    /// List users by tokens purchased
+    Users,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(Some(output)) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).unwrap_or_default()
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(None) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Option<Value>> {
+    let db = Database::open(&cli.db)
+        .with_context(|| format!("opening database {}", cli.db.display()))?;
+
+    let output = match &cli.command {
+        Command::Sync { reset, watch } => {
+            sync(
+                Indexer::new(RpcClient::new(resolve_url(&cli.url)), db),
+                *reset,
+                *watch,
+            )?;
+            return Ok(None);
+        }
+        Command::Polls { creator } => match creator {
+            Some(creator) => json!(db.polls_by_creator(&parse_pubkey(creator)?)?),
+            None => json!(db.polls()?),
+        },
+        Command::Poll { poll_id } => {
+            let poll = db
+                .poll(*poll_id)?
+                .ok_or_else(|| anyhow!("poll {} is not indexed", poll_id))?;
+            json!({ "poll": poll, "votes": db.votes_for_poll(*poll_id)? })
+        }
+        Command::Votes { poll, voter } => match (poll, voter) {
+            (Some(poll_id), _) => json!(db.votes_for_poll(*poll_id)?),
+            (None, Some(voter)) => json!(db.votes_by_voter(&parse_pubkey(voter)?)?),
+            (None, None) => unreachable!("clap requires --poll or --voter"),
+        },
+        Command::Purchases { buyer } => {
+            let buyer = buyer.as_deref().map(parse_pubkey).transpose()?;
+            json!(db.purchases(buyer.as_ref())?)
+        }
+        Command::Users => json!(db.users()?),
+    };
+
+    Ok(Some(output))
+}
+
+fn sync(mut indexer: Indexer, reset: bool, watch: Option<u64>) -> Result<()> {
+    if reset {
+        indexer.reset()?;
+    }
+
+    loop {
+        let stats = indexer.sync()?;
+        eprintln!(
+            "Synced {} polls, {} archived polls, {} votes, {} users; {} new transactions, {} purchases",
+            stats.polls,
+            stats.archived_polls,
+            stats.votes,
+            stats.users,
+            stats.transactions,
+            stats.purchases
+        );
+
+        match watch {
+            Some(seconds) => thread::sleep(Duration::from_secs(seconds)),
+            None => return Ok(()),
+        }
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey> {
+    Pubkey::from_str(value).map_err(|_| anyhow!("invalid public key: {}", value))
+}
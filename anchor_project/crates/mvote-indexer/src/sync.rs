@@ -0,0 +1,139 @@
+use anyhow::Result;
+use mvote::state::{Poll, PollSummary, UserStats, VoteRecord};
+use mvote_client::accounts;
+use mvote_client::events::{parse_logs, MvoteEvent};
+use mvote_client::rpc::{unix_now, RpcClient};
+
+use crate::db::Database;
+
+// Keeps a Database in step with the chain. Transactions are read
+// incrementally from the last indexed signature, then accounts are re-read in
+// full so the snapshot overrides whatever the events recorded for live
+// accounts.
+
+/// Maximum page size accepted by getSignaturesForAddress
+const SIGNATURE_PAGE: usize = 1000;
+
+#[derive(Debug, Default)]
+pub struct SyncStats {
+    pub polls: usize,
+    pub archived_polls: usize,
+    pub votes: usize,
+    pub users: usize,
+    pub transactions: usize,
+    pub purchases: usize,
+}
+
+pub struct Indexer {
+    rpc: RpcClient,
+    db: Database,
+}
+
+impl Indexer {
+    pub fn new(rpc: RpcClient, db: Database) -> Self {
+        Self { rpc, db }
+    }
+
+    pub fn db(&self) -> &Database {
+        &self.db
+    }
+
+    /// Drop everything indexed so far. The next sync replays all history.
+    pub fn reset(&mut self) -> Result<()> {
+        Ok(self.db.reset()?)
+    }
+
+    pub fn sync(&mut self) -> Result<SyncStats> {
+        let mut stats = SyncStats::default();
+        self.sync_transactions(&mut stats)?;
+        self.sync_accounts(&mut stats)?;
+        Ok(stats)
+    }
+
+    fn sync_accounts(&mut self, stats: &mut SyncStats) -> Result<()> {
+        // Legacy accounts that have not been migrated do not decode and are skipped
+        let polls: Vec<Poll> = self
+            .rpc
+            .get_program_accounts::<Poll>()?
+            .iter()
+            .filter_map(|(_, data)| accounts::decode_poll(data).ok())
+            .collect();
+        let summaries: Vec<PollSummary> = self
+            .rpc
+            .get_program_accounts::<PollSummary>()?
+            .iter()
+            .filter_map(|(_, data)| accounts::decode_poll_summary(data).ok())
+            .collect();
+        let votes: Vec<_> = self
+            .rpc
+            .get_program_accounts::<VoteRecord>()?
+            .iter()
+            .filter_map(|(address, data)| {
+                accounts::decode_vote_record(data)
+                    .ok()
+                    .map(|vote| (*address, vote))
+            })
+            .collect();
+        let users: Vec<UserStats> = self
+            .rpc
+            .get_program_accounts::<UserStats>()?
+            .iter()
+            .filter_map(|(_, data)| accounts::decode_user_stats(data).ok())
+            .collect();
+
+        self.db
+            .apply_account_snapshot(&polls, &summaries, &votes, &users, unix_now())?;
+
+        stats.polls = polls.len();
+        stats.archived_polls = summaries.len();
+        stats.votes = votes.len();
+        stats.users = users.len();
+        Ok(())
+    }
+
+    fn sync_transactions(&mut self, stats: &mut SyncStats) -> Result<()> {
+        let until = self.db.last_signature()?;
+
+        // Signatures come newest first; collect everything after the cursor
+        // and replay oldest first so the cursor only ever moves forward
+        let mut pending = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let page = self.rpc.get_signatures_for_address(
+                &mvote::ID,
+                before.as_deref(),
+                until.as_deref(),
+                SIGNATURE_PAGE,
+            )?;
+            let done = page.len() < SIGNATURE_PAGE;
+            before = page.last().map(|info| info.signature.clone());
+            pending.extend(page);
+            if done || before.is_none() {
+                break;
+            }
+        }
+
+        for info in pending.iter().rev() {
+            let mut events = Vec::new();
+
+            if !info.failed {
+                // A node that cannot return the transaction yet must not move
+                // the cursor past it; stop here and retry on the next sync
+                let Some(transaction) = self.rpc.get_transaction_logs(&info.signature)? else {
+                    break;
+                };
+                events = parse_logs(&transaction.logs);
+            }
+
+            self.db
+                .apply_transaction(&info.signature, info.slot, &events)?;
+            stats.transactions += 1;
+            stats.purchases += events
+                .iter()
+                .filter(|event| matches!(event, MvoteEvent::TokensPurchased(_)))
+                .count();
+        }
+
+        Ok(())
+    }
+}
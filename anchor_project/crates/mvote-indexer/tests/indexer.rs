@@ -0,0 +1,344 @@
+use anchor_lang::prelude::Pubkey;
+use mvote::events::{
+    PollArchived, PollCreated, PollFinalized, TokensPurchased, VoteCast, VoteChanged,
+    VoteCommitted, VoteRetracted,
+};
+use mvote::state::{
+    Poll, PollOutcome, PollStatus, PollSummary, UserStats, VoteMode, VoteRecord, VotingStyle,
+};
+use mvote_client::events::MvoteEvent;
+use mvote_client::test_utils::zeroed;
+use mvote_indexer::Database;
+
+fn poll(id: u64, creator: Pubkey) -> Poll {
+    let mut poll: Poll = zeroed();
+    poll.id = id;
+    poll.creator = creator;
+    poll.question = format!("Question {}", id);
+    poll.options = vec!["Yes".to_string(), "No".to_string()];
+    poll.vote_counts = vec![2, 1];
+    poll.status = PollStatus::Active;
+    poll.end_time = i64::MAX;
+    poll
+}
+
+fn vote(poll_id: u64, voter: Pubkey, option_index: u8) -> (Pubkey, VoteRecord) {
+    let mut vote: VoteRecord = zeroed();
+    vote.poll_id = poll_id;
+    vote.voter = voter;
+    vote.option_index = option_index;
+    vote.tokens_spent = 1_000_000;
+    vote.voted_at = 1_700_000_000 + option_index as i64;
+    (mvote_client::pda::vote_record(poll_id, &voter).0, vote)
+}
+
+#[test]
+fn snapshot_is_queryable_and_tracks_closed_accounts() {
+    let mut db = Database::open_in_memory().unwrap();
+    let creator = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+
+    let mut user: UserStats = zeroed();
+    user.wallet = alice;
+    user.total_tokens_purchased = 5_000_000;
+
+    let polls = [poll(0, creator), poll(1, Pubkey::new_unique())];
+    let votes = [vote(0, alice, 0), vote(0, bob, 1), vote(1, alice, 1)];
+    db.apply_account_snapshot(&polls, &[], &votes, &[user], 100)
+        .unwrap();
+
+    let indexed = db.polls().unwrap();
+    assert_eq!(indexed.len(), 2);
+    assert_eq!(indexed[0].options, ["Yes", "No"]);
+    assert_eq!(indexed[0].vote_counts, [2, 1]);
+    assert_eq!(indexed[0].status, "active");
+    assert_eq!(db.polls_by_creator(&creator).unwrap().len(), 1);
+
+    let poll_votes = db.votes_for_poll(0).unwrap();
+    assert_eq!(poll_votes.len(), 2);
+    assert_eq!(poll_votes[0].voter, alice.to_string());
+    assert_eq!(db.votes_by_voter(&alice).unwrap().len(), 2);
+    assert_eq!(db.users().unwrap()[0].total_tokens_purchased, 5_000_000);
+
+    // Alice's vote record on poll 1 was closed and poll 1 archived
+    db.apply_account_snapshot(&polls[..1], &[], &votes[..2], &[], 200)
+        .unwrap();
+    assert!(db.poll(1).unwrap().unwrap().is_closed);
+    assert!(!db.poll(0).unwrap().unwrap().is_closed);
+    let alice_votes = db.votes_by_voter(&alice).unwrap();
+    assert_eq!(alice_votes.len(), 2);
+    assert!(!alice_votes[0].is_closed);
+    assert!(alice_votes[1].is_closed);
+}
+
+#[test]
+fn purchases_are_idempotent_and_reset_clears_everything() {
+    let mut db = Database::open_in_memory().unwrap();
+    let buyer = Pubkey::new_unique();
+    let purchase = || {
+        MvoteEvent::TokensPurchased(TokensPurchased {
+            buyer,
+            buyer_token_account: Pubkey::new_unique(),
+            amount: 2_000_000,
+            sol_cost: 13_333_333,
+            sol_usd_rate: 15_000,
+            tokens_purchased_today: 2_000_000,
+            timestamp: 1_700_000_000,
+        })
+    };
+
+    assert_eq!(db.last_signature().unwrap(), None);
+    for _ in 0..2 {
+        db.apply_transaction("sig1", 10, &[purchase()]).unwrap();
+    }
+    db.apply_transaction("sig2", 11, &[]).unwrap();
+
+    let purchases = db.purchases(Some(&buyer)).unwrap();
+    assert_eq!(purchases.len(), 1);
+    assert_eq!(purchases[0].amount, 2_000_000);
+    assert!(db
+        .purchases(Some(&Pubkey::new_unique()))
+        .unwrap()
+        .is_empty());
+    assert_eq!(db.last_signature().unwrap().as_deref(), Some("sig2"));
+
+    db.apply_account_snapshot(&[poll(0, buyer)], &[], &[], &[], 0)
+        .unwrap();
+    db.reset().unwrap();
+    assert!(db.polls().unwrap().is_empty());
+    assert!(db.purchases(None).unwrap().is_empty());
+    assert_eq!(db.last_signature().unwrap(), None);
+}
+
+fn poll_created(poll_id: u64, creator: Pubkey) -> MvoteEvent {
+    MvoteEvent::PollCreated(PollCreated {
+        poll_id,
+        poll: mvote_client::pda::poll(poll_id).0,
+        creator,
+        question: "Archived?".to_string(),
+        options: vec!["Yes".to_string(), "No".to_string()],
+        vote_mode: VoteMode::HoldingVote,
+        voting_style: VotingStyle::Binary,
+        status: PollStatus::Active,
+        start_time: 1_700_000_000,
+        end_time: 1_700_003_600,
+        is_secret: false,
+        reveal_end_time: 0,
+        creation_cost: 0,
+        timestamp: 1_700_000_000,
+    })
+}
+
+fn vote_cast(poll_id: u64, voter: Pubkey, timestamp: i64) -> MvoteEvent {
+    MvoteEvent::VoteCast(VoteCast {
+        poll_id,
+        poll: mvote_client::pda::poll(poll_id).0,
+        voter,
+        vote_record: mvote_client::pda::vote_record(poll_id, &voter).0,
+        option_index: 1,
+        option_mask: 0b10,
+        tokens_spent: 1_000_000,
+        vote_weight: 1,
+        timestamp,
+    })
+}
+
+#[test]
+fn events_restore_archived_polls_and_mark_retracted_votes() {
+    let mut db = Database::open_in_memory().unwrap();
+    let creator = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+
+    let created = poll_created(7, creator);
+    let retracted = MvoteEvent::VoteRetracted(VoteRetracted {
+        poll_id: 7,
+        voter: alice,
+        tokens_spent: 1_000_000,
+        timestamp: 1_700_000_020,
+    });
+    let archived = MvoteEvent::PollArchived(PollArchived {
+        poll_id: 7,
+        poll: mvote_client::pda::poll(7).0,
+        poll_summary: Pubkey::new_unique(),
+        creator,
+        timestamp: 1_700_090_000,
+    });
+
+    db.apply_transaction("sig1", 1, &[created]).unwrap();
+    db.apply_transaction(
+        "sig2",
+        2,
+        &[
+            vote_cast(7, alice, 1_700_000_010),
+            vote_cast(7, bob, 1_700_000_011),
+        ],
+    )
+    .unwrap();
+    db.apply_transaction("sig3", 3, &[retracted]).unwrap();
+    db.apply_transaction("sig4", 4, &[archived]).unwrap();
+    // None of these accounts exist any more
+    db.apply_account_snapshot(&[], &[], &[], &[], 1_700_100_000)
+        .unwrap();
+
+    let poll = db.poll(7).unwrap().unwrap();
+    assert_eq!(poll.question, "Archived?");
+    assert_eq!(poll.creator, creator.to_string());
+    assert!(poll.is_closed);
+
+    let votes = db.votes_for_poll(7).unwrap();
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes[0].voter, alice.to_string());
+    assert!(votes[0].is_closed && votes[0].is_retracted);
+    assert!(votes[1].is_closed && !votes[1].is_retracted);
+    assert_eq!(votes[1].tokens_spent, 1_000_000);
+
+    // Voting again after a retraction reopens the record
+    db.apply_transaction("sig5", 5, &[vote_cast(7, alice, 1_700_000_030)])
+        .unwrap();
+    let alice_vote = &db.votes_by_voter(&alice).unwrap()[0];
+    assert!(!alice_vote.is_closed && !alice_vote.is_retracted);
+    assert_eq!(alice_vote.voted_at, 1_700_000_030);
+}
+
+#[test]
+fn snapshot_overrides_events_for_live_accounts() {
+    let mut db = Database::open_in_memory().unwrap();
+    let creator = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+
+    db.apply_transaction("sig1", 1, &[vote_cast(0, alice, 1_700_000_000)])
+        .unwrap();
+    db.apply_account_snapshot(&[poll(0, creator)], &[], &[vote(0, alice, 0)], &[], 100)
+        .unwrap();
+    // Replaying an applied transaction does not touch the snapshot
+    db.apply_transaction("sig1", 1, &[vote_cast(0, alice, 1_700_000_000)])
+        .unwrap();
+
+    let vote = &db.votes_for_poll(0).unwrap()[0];
+    assert_eq!(vote.option_index, 0);
+    assert!(!vote.is_closed && !vote.is_retracted);
+    assert_eq!(db.poll(0).unwrap().unwrap().vote_counts, [2, 1]);
+}
+
+#[test]
+fn events_rebuild_results_and_repeat_votes_of_archived_polls() {
+    let mut db = Database::open_in_memory().unwrap();
+    let creator = Pubkey::new_unique();
+    let alice = Pubkey::new_unique();
+
+    let changed = MvoteEvent::VoteChanged(VoteChanged {
+        poll_id: 8,
+        voter: alice,
+        old_option_index: 1,
+        new_option_index: 0,
+        timestamp: 1_700_000_020,
+    });
+    let finalized = MvoteEvent::PollFinalized(PollFinalized {
+        poll_id: 8,
+        poll: mvote_client::pda::poll(8).0,
+        winning_option: Some(0),
+        is_tie: false,
+        outcome: PollOutcome::Passed,
+        vote_counts: vec![2, 0],
+        total_votes: 2,
+        total_tokens_spent: 2_000_000,
+        unrevealed_votes: 0,
+        timestamp: 1_700_004_000,
+    });
+
+    db.apply_transaction("sig1", 1, &[poll_created(8, creator)])
+        .unwrap();
+    // Alice votes twice on the same record, then moves her votes
+    db.apply_transaction("sig2", 2, &[vote_cast(8, alice, 1_700_000_010)])
+        .unwrap();
+    db.apply_transaction("sig3", 3, &[vote_cast(8, alice, 1_700_000_015)])
+        .unwrap();
+    db.apply_transaction("sig4", 4, &[changed]).unwrap();
+    db.apply_transaction("sig5", 5, &[finalized]).unwrap();
+    db.apply_account_snapshot(&[], &[], &[], &[], 1_700_100_000)
+        .unwrap();
+
+    let poll = db.poll(8).unwrap().unwrap();
+    assert_eq!(poll.status, "finalized");
+    assert_eq!(poll.vote_counts, [2, 0]);
+    assert_eq!(poll.total_votes, 2);
+    assert_eq!(poll.winning_option, Some(0));
+    assert_eq!(poll.outcome, "passed");
+    assert_eq!(poll.finalized_at, 1_700_004_000);
+
+    let vote = &db.votes_for_poll(8).unwrap()[0];
+    assert_eq!(vote.vote_count, 2);
+    assert_eq!(vote.tokens_spent, 2_000_000);
+    assert_eq!(vote.option_index, 0);
+    assert_eq!(vote.voted_at, 1_700_000_015);
+}
+
+#[test]
+fn revealing_a_commitment_counts_the_vote() {
+    let mut db = Database::open_in_memory().unwrap();
+    let alice = Pubkey::new_unique();
+
+    let committed = MvoteEvent::VoteCommitted(VoteCommitted {
+        poll_id: 9,
+        voter: alice,
+        vote_record: mvote_client::pda::vote_record(9, &alice).0,
+        tokens_spent: 1_000_000,
+        timestamp: 1_700_000_000,
+    });
+    db.apply_transaction("sig1", 1, &[committed]).unwrap();
+    let vote = &db.votes_for_poll(9).unwrap()[0];
+    assert_eq!(vote.vote_count, 0);
+    assert!(!vote.is_revealed);
+
+    let MvoteEvent::VoteCast(mut revealed) = vote_cast(9, alice, 1_700_010_000) else {
+        unreachable!()
+    };
+    revealed.tokens_spent = 0;
+    db.apply_transaction("sig2", 2, &[MvoteEvent::VoteCast(revealed)])
+        .unwrap();
+
+    let vote = &db.votes_for_poll(9).unwrap()[0];
+    assert_eq!(vote.vote_count, 1);
+    assert_eq!(vote.tokens_spent, 1_000_000);
+    assert_eq!(vote.option_index, 1);
+    assert!(vote.is_revealed);
+}
+
+#[test]
+fn summaries_refresh_archived_polls() {
+    let mut db = Database::open_in_memory().unwrap();
+    let creator = Pubkey::new_unique();
+
+    let mut summary: PollSummary = zeroed();
+    summary.poll_id = 7;
+    summary.creator = creator;
+    summary.status = PollStatus::Finalized;
+    summary.outcome = PollOutcome::Failed;
+    summary.option_count = 2;
+    summary.vote_counts = [4, 3, 0, 0];
+    summary.total_votes = 7;
+    summary.is_tie = false;
+    summary.winning_option = Some(0);
+
+    // Without the poll's history only the summary's results are known
+    let mut other = summary.clone();
+    other.poll_id = 6;
+    db.apply_account_snapshot(&[], &[summary, other], &[], &[], 100)
+        .unwrap();
+    db.apply_transaction("sig1", 1, &[poll_created(7, creator)])
+        .unwrap();
+
+    let unknown = db.poll(6).unwrap().unwrap();
+    assert_eq!(unknown.question, "");
+    assert_eq!(unknown.options, ["", ""]);
+    assert!(unknown.is_closed);
+
+    let poll = db.poll(7).unwrap().unwrap();
+    assert_eq!(poll.vote_counts, [4, 3]);
+    assert_eq!(poll.status, "finalized");
+    assert_eq!(poll.outcome, "failed");
+    assert_eq!(poll.total_votes, 7);
+    assert!(poll.is_closed);
+}
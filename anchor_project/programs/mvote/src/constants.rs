@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 // Seeds for PDAs
 pub const CONFIG_SEED: &[u8] = b"config";
 pub const MINT_SEED: &[u8] = b"mint";
@@ -5,6 +7,9 @@ pub const VAULT_SEED: &[u8] = b"vault";
 pub const USER_SEED: &[u8] = b"user";
 pub const POLL_SEED: &[u8] = b"poll";
 pub const VOTE_SEED: &[u8] = b"vote";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const BALLOTS_SEED: &[u8] = b"ballots";
+pub const SUMMARY_SEED: &[u8] = b"summary";
 
 // Token configuration
 pub const TOKEN_DECIMALS: u8 = 6;
@@ -12,6 +17,7 @@ pub const TOKENS_PER_USD: u64 = 1_000_000; // 1 token = 1 USD (with 6 decimals)
 
 // Limits
 pub const DAILY_PURCHASE_LIMIT: u64 = 100_000_000; // 100 tokens (with 6 decimals)
+pub const DAILY_REDEEM_LIMIT: u64 = 50_000_000; // 50 tokens
 pub const POLL_CREATION_COST: u64 = 10_000_000; // 10 tokens
 pub const VOTE_COST: u64 = 1_000_000; // 1 token
 
@@ -22,11 +28,41 @@ pub const MIN_OPTIONS: usize = 2;
 pub const MAX_OPTIONS: usize = 4;
 pub const MIN_DURATION_MINUTES: u16 = 10;
 pub const MAX_DURATION_MINUTES: u16 = 1440; // 24 hours
+pub const MAX_RANKED_BALLOTS: usize = 64; // distinct orderings of 1-4 options
+pub const MAX_TALLY_ROUNDS: usize = MAX_OPTIONS - 1;
+pub const MIN_REVEAL_MINUTES: u16 = 10;
+pub const MAX_REVEAL_MINUTES: u16 = 1440; // 24 hours
+pub const DEFAULT_MAX_START_LEAD_TIME: u64 = 7 * 86400; // 7 days
+pub const MAX_START_LEAD_TIME: u64 = 90 * 86400; // 90 days
+pub const MIN_POLL_RETENTION_PERIOD: u64 = 86400; // 1 day
+pub const DEFAULT_POLL_RETENTION_PERIOD: u64 = 30 * 86400; // 30 days
+pub const MAX_POLL_RETENTION_PERIOD: u64 = 365 * 86400; // 1 year
 
 // Time
 pub const SGT_OFFSET_SECONDS: i64 = 8 * 60 * 60; // UTC+8
 pub const SECONDS_PER_DAY: i64 = 86400;
 
-// Fixed SOL/USD rate for simplicity (can be replaced with oracle)
+// Admin config bounds (enforced by update_config)
+pub const MIN_SOL_USD_RATE: u64 = 100; // $1.00
+pub const MAX_SOL_USD_RATE: u64 = 10_000_000; // $100,000.00
+pub const MAX_TOKENS_PER_USD: u64 = 1_000_000_000; // 1000 tokens per USD
+pub const MAX_DAILY_PURCHASE_LIMIT: u64 = 1_000_000_000_000; // 1M tokens
+pub const MAX_POLL_CREATION_COST: u64 = 1_000_000_000; // 1000 tokens
+pub const MAX_VOTE_COST: u64 = 100_000_000; // 100 tokens
+pub const MAX_REDEEM_SPREAD_BPS: u16 = 5_000; // 50%
+
+// Redemption
+pub const REDEEM_SPREAD_BPS: u16 = 500; // 5%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Fixed SOL/USD rate, used when no price oracle is configured
 // Representing $150.00 as 15000 (2 decimal places)
 pub const SOL_USD_RATE: u64 = 15000; // $150.00 per SOL
+
+// Oracle (Pyth pull oracle PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 60; // seconds
+pub const MAX_PRICE_AGE: u64 = 3600; // seconds
+pub const MAX_PRICE_CLOCK_DRIFT: i64 = 10; // seconds a price may be ahead of the cluster clock
+pub const DEFAULT_MAX_PRICE_CONF_BPS: u16 = 200; // 2%
+pub const MAX_PRICE_CONF_BPS: u16 = 10_000; // 100%
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 #[error_code]
 pub enum MvoteError {
-    #[msg("Daily purchase limit exceeded (100 mVote max)")]
+    #[msg("Daily purchase limit exceeded")]
     DailyLimitExceeded,
 
     #[msg("Insufficient SOL balance")]
@@ -49,4 +49,154 @@ pub enum MvoteError {
 
     #[msg("Invalid amount")]
     InvalidAmount,
+
+    #[msg("Only the program admin can perform this action")]
+    UnauthorizedAdmin,
+
+    #[msg("Config value out of allowed range")]
+    InvalidConfigValue,
+
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    #[msg("Destination does not match the configured treasury")]
+    InvalidTreasury,
+
+    #[msg("Insufficient SOL in vault")]
+    InsufficientVaultBalance,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Price oracle account is missing or invalid")]
+    InvalidOracleAccount,
+
+    #[msg("Oracle price is stale")]
+    StalePrice,
+
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Oracle price is invalid")]
+    InvalidPrice,
+
+    #[msg("SOL cost exceeds the maximum lamports allowed")]
+    SlippageExceeded,
+
+    #[msg("Daily redemption limit exceeded")]
+    DailyRedeemLimitExceeded,
+
+    #[msg("Vote changes are disabled for this poll")]
+    VoteChangesDisabled,
+
+    #[msg("Escrow token account is required for EscrowVote polls")]
+    EscrowAccountRequired,
+
+    #[msg("Poll does not use escrowed votes")]
+    NotEscrowPoll,
+
+    #[msg("Poll is still accepting votes")]
+    PollStillActive,
+
+    #[msg("Tokens already reclaimed or refunded")]
+    TokensAlreadyReclaimed,
+
+    #[msg("Escrowed votes cannot be retracted")]
+    EscrowRetractNotAllowed,
+
+    #[msg("Ranked-choice polls must use FairVote")]
+    RankedChoiceRequiresFairVote,
+
+    #[msg("Ranked-choice polls require a ranked ballot")]
+    RankedBallotRequired,
+
+    #[msg("Poll is not a ranked-choice poll")]
+    NotRankedPoll,
+
+    #[msg("Rankings must list distinct, valid options")]
+    InvalidRankings,
+
+    #[msg("Poll has already been tallied")]
+    AlreadyTallied,
+
+    #[msg("Ranked ballots account is required for ranked-choice polls")]
+    RankedBallotsAccountRequired,
+
+    #[msg("Approval polls must use FairVote")]
+    ApprovalRequiresFairVote,
+
+    #[msg("Approval polls require an option bitmask")]
+    ApprovalBallotRequired,
+
+    #[msg("Poll is not an approval poll")]
+    NotApprovalPoll,
+
+    #[msg("Option bitmask must select at least one valid option")]
+    InvalidOptionMask,
+
+    #[msg("Secret ballots require FairVote with Binary or MultipleChoice")]
+    UnsupportedSecretBallot,
+
+    #[msg("Secret-ballot polls require a vote commitment")]
+    CommitmentRequired,
+
+    #[msg("Poll does not use secret ballots")]
+    NotSecretPoll,
+
+    #[msg("Reveal window is not open")]
+    RevealWindowClosed,
+
+    #[msg("Vote already revealed")]
+    AlreadyRevealed,
+
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+
+    #[msg("Reveal duration must be 10-1440 minutes")]
+    InvalidRevealDuration,
+
+    #[msg("Start time must be between now and the maximum lead time")]
+    InvalidStartTime,
+
+    #[msg("Poll has already been finalized")]
+    AlreadyFinalized,
+
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+
+    #[msg("Ranked-choice polls must be tallied before finalization")]
+    RankedTallyRequired,
+
+    #[msg("Pass threshold must be 1-10000 bps and quorum above zero")]
+    InvalidPassRules,
+
+    #[msg("Poll has been cancelled")]
+    PollCancelled,
+
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Poll has not been cancelled")]
+    PollNotCancelled,
+
+    #[msg("Poll must be finalized or cancelled")]
+    PollNotFinished,
+
+    #[msg("Poll is still within its retention period")]
+    RetentionPeriodActive,
+
+    #[msg("Poll or poll summary account is required")]
+    PollAccountRequired,
+
+    #[msg("Reclaim escrowed or refunded tokens before closing the vote record")]
+    TokensNotReclaimed,
+
+    #[msg("Price account holds a different feed than the configured one")]
+    PriceFeedMismatch,
+
+    #[msg("Escrowed tokens or refunds are still unclaimed")]
+    UnclaimedTokensRemain,
+
+    #[msg("Price was published after the current time")]
+    PriceFromFuture,
 }
@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::UpdateConfigParams;
+use crate::state::{PollOutcome, PollStatus, VoteMode, VotingStyle};
+
+// Typed events for every state change, decodable from the IDL.
+// All timestamps are unix timestamps from the Clock sysvar.
+
+#[event]
+pub struct Initialized {
+    pub admin: Pubkey,
+    pub config: Pubkey,
+    pub token_mint: Pubkey,
+    pub sol_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensPurchased {
+    pub buyer: Pubkey,
+    pub buyer_token_account: Pubkey,
+    /// Tokens minted (with decimals)
+    pub amount: u64,
+    /// Lamports paid into the vault
+    pub sol_cost: u64,
+    /// SOL/USD rate in cents used for pricing
+    pub sol_usd_rate: u64,
+    /// Tokens purchased today after this purchase
+    pub tokens_purchased_today: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensRedeemed {
+    pub redeemer: Pubkey,
+    /// Tokens burned (with decimals)
+    pub amount: u64,
+    /// Lamports paid out of the vault after the spread
+    pub payout: u64,
+    pub sol_usd_rate: u64,
+    pub redeem_spread_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollCreated {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub creator: Pubkey,
+    pub question: String,
+    pub options: Vec<String>,
+    pub vote_mode: VoteMode,
+    pub voting_style: VotingStyle,
+    pub status: PollStatus,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub is_secret: bool,
+    /// End of the reveal window (0 for non-secret polls)
+    pub reveal_end_time: i64,
+    /// Tokens burned to create the poll
+    pub creation_cost: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub voter: Pubkey,
+    pub vote_record: Pubkey,
+    /// Chosen option (first preference for ranked ballots, lowest approved
+    /// option for approval ballots)
+    pub option_index: u8,
+    /// Bit i set for every option whose count changed
+    pub option_mask: u8,
+    /// Tokens burned or escrowed by this vote (0 when revealing a commitment)
+    pub tokens_spent: u64,
+    /// Amount added to each selected option's vote count
+    pub vote_weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCommitted {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub vote_record: Pubkey,
+    pub tokens_spent: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteChanged {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub old_option_index: u8,
+    pub new_option_index: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRetracted {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    /// Tokens spent on the retracted vote (not refunded)
+    pub tokens_spent: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteChangesSet {
+    pub poll_id: u64,
+    pub allowed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollClosed {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub creator: Pubkey,
+    pub vote_counts: Vec<u64>,
+    pub total_votes: u64,
+    pub total_tokens_spent: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollCancelled {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub creator: Pubkey,
+    /// Tokens refundable through claim_refund
+    pub total_tokens_spent: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RankedTallied {
+    pub poll_id: u64,
+    pub winning_option: Option<u8>,
+    pub tally_rounds: Vec<[u64; 4]>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollFinalized {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub winning_option: Option<u8>,
+    pub is_tie: bool,
+    pub outcome: PollOutcome,
+    pub vote_counts: Vec<u64>,
+    pub total_votes: u64,
+    pub total_tokens_spent: u64,
+    /// Secret-ballot commitments that were never revealed
+    pub unrevealed_votes: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowReclaimed {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub amount: u64,
+    /// Returned from escrow rather than re-minted
+    pub from_escrow: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRecordClosed {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub vote_record: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollArchived {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub poll_summary: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PollMigrated {
+    pub poll_id: u64,
+    pub poll: Pubkey,
+    pub status: PollStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub admin: Pubkey,
+    pub config: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserStatsMigrated {
+    pub wallet: Pubkey,
+    pub user_stats: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRecordMigrated {
+    pub poll_id: u64,
+    pub voter: Pubkey,
+    pub vote_record: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    /// Fields that were set (None = unchanged)
+    pub params: UpdateConfigParams,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    /// Default pubkey when a pending transfer is cancelled
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseSet {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
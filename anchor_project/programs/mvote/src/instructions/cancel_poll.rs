@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::PollCancelled;
+use crate::state::{Poll, PollStatus};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct CancelPoll<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump,
+        constraint = poll.creator == creator.key() @ MvoteError::Unauthorized
+    )]
+    pub poll: Account<'info, Poll>,
+}
+
+pub fn handler(ctx: Context<CancelPoll>, poll_id: u64) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let clock = Clock::get()?;
+
+    // Only polls still collecting votes can be cancelled; once voting has
+    // ended the creator could otherwise veto a result they dislike
+    match poll.sync_status(clock.unix_timestamp) {
+        PollStatus::Scheduled | PollStatus::Active => {}
+        PollStatus::Finalized => return err!(MvoteError::AlreadyFinalized),
+        PollStatus::Cancelled => return err!(MvoteError::PollCancelled),
+        PollStatus::ClosedEarly | PollStatus::Expired => return err!(MvoteError::PollNotActive),
+    }
+
+    // Votes become refundable through claim_refund, the creation cost is not
+    poll.status = PollStatus::Cancelled;
+    poll.cancelled_at = clock.unix_timestamp;
+
+    msg!(
+        "Poll {} cancelled by creator, {} tokens refundable",
+        poll_id,
+        poll.total_tokens_spent
+    );
+
+    emit!(PollCancelled {
+        poll_id,
+        poll: poll.key(),
+        creator: poll.creator,
+        total_tokens_spent: poll.total_tokens_spent,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
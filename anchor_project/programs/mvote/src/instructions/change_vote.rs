@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteChanged;
+use crate::state::{Config, Poll, VoteRecord, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct ChangeVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+pub fn handler(ctx: Context<ChangeVote>, poll_id: u64, new_option_index: u8) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let poll = &mut ctx.accounts.poll;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(!config.paused, MvoteError::ProgramPaused);
+
+    // Check poll is active and accepts vote changes
+    poll.require_voting_open(current_time)?;
+    require!(poll.allow_vote_changes, MvoteError::VoteChangesDisabled);
+
+    // Ranked and approval ballots cannot be moved to a single option
+    require!(
+        poll.voting_style != VotingStyle::RankedChoice
+            && poll.voting_style != VotingStyle::Approval,
+        MvoteError::VoteChangesDisabled
+    );
+
+    // Secret ballots are fixed once committed
+    require!(!poll.is_secret, MvoteError::VoteChangesDisabled);
+
+    // Check valid option
+    require!(
+        (new_option_index as usize) < poll.options.len(),
+        MvoteError::InvalidOption
+    );
+
+    // Remove the record's weight from every previous option
+    for option in 0..poll.options.len() {
+        let weight = vote_record.weight_for(poll.vote_mode, option);
+        poll.vote_counts[option] = poll.vote_counts[option]
+            .checked_sub(weight)
+            .ok_or(MvoteError::Overflow)?;
+    }
+
+    // Move all tokens to the new option
+    let old_option_index = vote_record.option_index;
+    vote_record.option_index = new_option_index;
+    vote_record.option_tokens = [0; 4];
+    vote_record.option_tokens[new_option_index as usize] = vote_record.tokens_spent;
+
+    // Add the record's weight back on the new option
+    let new_weight = vote_record.weight_for(poll.vote_mode, new_option_index as usize);
+    poll.vote_counts[new_option_index as usize] = poll.vote_counts[new_option_index as usize]
+        .checked_add(new_weight)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Vote on poll {} changed from option {} to option {}",
+        poll_id,
+        old_option_index,
+        new_option_index
+    );
+
+    emit!(VoteChanged {
+        poll_id,
+        voter: vote_record.voter,
+        old_option_index,
+        new_option_index,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::RefundClaimed;
+use crate::state::{Config, Poll, PollStatus, VoteMode, VoteRecord};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct ClaimRefund<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Per-poll escrow, only used by EscrowVote polls
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, poll_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = poll
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>, poll_id: u64) -> Result<()> {
+    let poll = &ctx.accounts.poll;
+    let vote_record = &mut ctx.accounts.vote_record;
+
+    require!(
+        poll.status == PollStatus::Cancelled,
+        MvoteError::PollNotCancelled
+    );
+
+    require!(
+        !vote_record.tokens_reclaimed,
+        MvoteError::TokensAlreadyReclaimed
+    );
+
+    if poll.vote_mode == VoteMode::EscrowVote {
+        // Return escrowed tokens, signed by the poll PDA
+        let escrow_token_account = ctx
+            .accounts
+            .escrow_token_account
+            .as_ref()
+            .ok_or(MvoteError::EscrowAccountRequired)?;
+
+        let poll_id_bytes = poll_id.to_le_bytes();
+        let poll_seeds = &[POLL_SEED, poll_id_bytes.as_ref(), &[poll.bump]];
+        let signer_seeds = &[&poll_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: escrow_token_account.to_account_info(),
+                    to: ctx.accounts.voter_token_account.to_account_info(),
+                    authority: ctx.accounts.poll.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            vote_record.tokens_spent,
+        )?;
+    } else {
+        // Re-mint burned tokens, signed by the config PDA
+        let config_seeds = &[CONFIG_SEED, &[ctx.accounts.config.bump]];
+        let signer_seeds = &[&config_seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.voter_token_account.to_account_info(),
+                    authority: ctx.accounts.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            vote_record.tokens_spent,
+        )?;
+    }
+
+    vote_record.tokens_reclaimed = true;
+
+    let poll = &mut ctx.accounts.poll;
+    poll.outstanding_records = poll
+        .outstanding_records
+        .checked_sub(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Refunded {} tokens from cancelled poll {}",
+        vote_record.tokens_spent,
+        poll_id
+    );
+
+    emit!(RefundClaimed {
+        poll_id,
+        voter: vote_record.voter,
+        amount: vote_record.tokens_spent,
+        from_escrow: poll.vote_mode == VoteMode::EscrowVote,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
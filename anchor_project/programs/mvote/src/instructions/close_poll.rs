@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::MvoteError;
-use crate::state::Poll;
+use crate::events::PollClosed;
+use crate::state::{Poll, PollStatus};
 
 #[derive(Accounts)]
 #[instruction(poll_id: u64)]
@@ -20,14 +21,28 @@ pub struct ClosePoll<'info> {
 
 pub fn handler(ctx: Context<ClosePoll>, _poll_id: u64) -> Result<()> {
     let poll = &mut ctx.accounts.poll;
+    let clock = Clock::get()?;
 
     // Check poll is still active
-    require!(poll.is_active, MvoteError::PollNotActive);
+    require!(
+        poll.sync_status(clock.unix_timestamp) == PollStatus::Active,
+        MvoteError::PollNotActive
+    );
 
     // Close the poll
-    poll.is_active = false;
+    poll.status = PollStatus::ClosedEarly;
 
     msg!("Poll {} closed by creator", poll.id);
 
+    emit!(PollClosed {
+        poll_id: poll.id,
+        poll: poll.key(),
+        creator: poll.creator,
+        vote_counts: poll.vote_counts.clone(),
+        total_votes: poll.total_votes,
+        total_tokens_spent: poll.total_tokens_spent,
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
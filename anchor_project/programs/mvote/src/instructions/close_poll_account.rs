@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::PollArchived;
+use crate::state::{Config, Poll, PollStatus, PollSummary, RankedBallots, VoteMode, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct ClosePollAccount<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump,
+        constraint = poll.creator == creator.key() @ MvoteError::Unauthorized
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PollSummary::SIZE,
+        seeds = [SUMMARY_SEED, poll_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub poll_summary: Account<'info, PollSummary>,
+
+    /// Aggregated ballots, closed alongside RankedChoice polls
+    #[account(
+        mut,
+        close = creator,
+        seeds = [BALLOTS_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = ranked_ballots.bump
+    )]
+    pub ranked_ballots: Option<Account<'info, RankedBallots>>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Escrow of an EscrowVote poll that received votes, closed with the poll
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, poll_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = poll
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClosePollAccount>, poll_id: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let poll = &ctx.accounts.poll;
+    let poll_summary = &mut ctx.accounts.poll_summary;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let finished_at = match poll.status {
+        PollStatus::Finalized => poll.finalized_at,
+        PollStatus::Cancelled => poll.cancelled_at,
+        _ => return err!(MvoteError::PollNotFinished),
+    };
+
+    // Closing the poll would strand escrowed tokens and unclaimed refunds
+    if poll.status == PollStatus::Cancelled || poll.vote_mode == VoteMode::EscrowVote {
+        require!(
+            poll.outstanding_records == 0,
+            MvoteError::UnclaimedTokensRemain
+        );
+    }
+
+    // The escrow is created by the first vote
+    if ctx.accounts.escrow_token_account.is_none() {
+        require!(
+            poll.vote_mode != VoteMode::EscrowVote || poll.total_tokens_spent == 0,
+            MvoteError::EscrowAccountRequired
+        );
+    }
+
+    // Voters keep access to escrow reclaims and refunds until retention ends
+    let retention_end = finished_at
+        .checked_add(config.poll_retention_period as i64)
+        .ok_or(MvoteError::Overflow)?;
+    require!(
+        current_time >= retention_end,
+        MvoteError::RetentionPeriodActive
+    );
+
+    if poll.voting_style == VotingStyle::RankedChoice {
+        require!(
+            ctx.accounts.ranked_ballots.is_some(),
+            MvoteError::RankedBallotsAccountRequired
+        );
+    }
+
+    // Return the escrow rent, signed by the poll PDA
+    if let Some(escrow_token_account) = &ctx.accounts.escrow_token_account {
+        let poll_id_bytes = poll_id.to_le_bytes();
+        let poll_seeds = &[POLL_SEED, poll_id_bytes.as_ref(), &[poll.bump]];
+        let signer_seeds = &[&poll_seeds[..]];
+
+        // Every vote record has settled, so anything left was sent to the
+        // escrow directly and would otherwise block closing it
+        if escrow_token_account.amount > 0 {
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        from: escrow_token_account.to_account_info(),
+                        authority: poll.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                escrow_token_account.amount,
+            )?;
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: escrow_token_account.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: poll.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    // Archive the results
+    let mut vote_counts = [0u64; 4];
+    vote_counts[..poll.vote_counts.len()].copy_from_slice(&poll.vote_counts);
+
+    poll_summary.poll_id = poll_id;
+    poll_summary.creator = poll.creator;
+    poll_summary.vote_mode = poll.vote_mode;
+    poll_summary.voting_style = poll.voting_style;
+    poll_summary.status = poll.status;
+    poll_summary.outcome = poll.outcome;
+    poll_summary.winning_option = poll.winning_option;
+    poll_summary.is_tie = poll.is_tie;
+    poll_summary.option_count = poll.options.len() as u8;
+    poll_summary.vote_counts = vote_counts;
+    poll_summary.total_votes = poll.total_votes;
+    poll_summary.total_tokens_spent = poll.total_tokens_spent;
+    poll_summary.end_time = poll.end_time;
+    poll_summary.finalized_at = poll.finalized_at;
+    poll_summary.cancelled_at = poll.cancelled_at;
+    poll_summary.archived_at = current_time;
+    poll_summary.bump = ctx.bumps.poll_summary;
+
+    msg!(
+        "Poll {} archived and closed by creator {}",
+        poll_id,
+        poll.creator
+    );
+
+    emit!(PollArchived {
+        poll_id,
+        poll: poll.key(),
+        poll_summary: poll_summary.key(),
+        creator: poll.creator,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
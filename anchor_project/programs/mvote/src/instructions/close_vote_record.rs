@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteRecordClosed;
+use crate::state::{Poll, PollStatus, PollSummary, VoteMode, VoteRecord};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct CloseVoteRecord<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Poll, while its account is still open
+    #[account(
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Option<Account<'info, Poll>>,
+
+    /// Archived poll, once close_poll_account has run
+    #[account(
+        seeds = [SUMMARY_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll_summary.bump
+    )]
+    pub poll_summary: Option<Account<'info, PollSummary>>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+pub fn handler(ctx: Context<CloseVoteRecord>, poll_id: u64) -> Result<()> {
+    let vote_record = &ctx.accounts.vote_record;
+
+    match (&ctx.accounts.poll, &ctx.accounts.poll_summary) {
+        (Some(poll), _) => {
+            require!(
+                matches!(poll.status, PollStatus::Finalized | PollStatus::Cancelled),
+                MvoteError::PollNotFinished
+            );
+
+            // Escrowed tokens and refunds are claimed through the record
+            require!(
+                vote_record.tokens_reclaimed
+                    || (poll.vote_mode != VoteMode::EscrowVote
+                        && poll.status != PollStatus::Cancelled),
+                MvoteError::TokensNotReclaimed
+            );
+        }
+        // Poll account already archived
+        (None, Some(_)) => {}
+        (None, None) => return err!(MvoteError::PollAccountRequired),
+    }
+
+    msg!(
+        "Vote record for poll {} closed by {}",
+        poll_id,
+        vote_record.voter
+    );
+
+    emit!(VoteRecordClosed {
+        poll_id,
+        voter: vote_record.voter,
+        vote_record: vote_record.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
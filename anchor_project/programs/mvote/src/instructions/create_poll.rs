@@ -3,7 +3,20 @@ use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
 
 use crate::constants::*;
 use crate::errors::MvoteError;
-use crate::state::{Config, Poll, UserStats, VoteMode, VotingStyle};
+use crate::events::PollCreated;
+use crate::state::{
+    Config, Poll, PollOutcome, PollStatus, Quorum, RankedBallots, UserStats, VoteMode, VotingStyle,
+};
+
+/// Rules deciding whether a finalized poll passes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PassRules {
+    /// Minimum participation (None = no quorum)
+    pub quorum: Option<Quorum>,
+    /// Winner's share of the vote required to pass, in basis points
+    /// (e.g. 6667 for two-thirds)
+    pub pass_threshold_bps: u16,
+}
 
 #[derive(Accounts)]
 pub struct CreatePoll<'info> {
@@ -48,10 +61,21 @@ pub struct CreatePoll<'info> {
     )]
     pub creator_token_account: Account<'info, TokenAccount>,
 
+    /// Aggregated ballots, only used by RankedChoice polls
+    #[account(
+        init,
+        payer = creator,
+        space = RankedBallots::SIZE,
+        seeds = [BALLOTS_SEED, config.total_polls_created.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub ranked_ballots: Option<Account<'info, RankedBallots>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreatePoll>,
     question: String,
@@ -59,12 +83,17 @@ pub fn handler(
     vote_mode: VoteMode,
     voting_style: VotingStyle,
     duration_minutes: u16,
+    start_time: Option<i64>,
+    pass_rules: Option<PassRules>,
+    reveal_duration_minutes: Option<u16>,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let user_stats = &mut ctx.accounts.user_stats;
     let poll = &mut ctx.accounts.poll;
     let clock = Clock::get()?;
 
+    require!(!config.paused, MvoteError::ProgramPaused);
+
     // Validate question length
     require!(
         !question.is_empty() && question.len() <= MAX_QUESTION_LENGTH,
@@ -87,10 +116,85 @@ pub fn handler(
 
     // Validate duration
     require!(
-        duration_minutes >= MIN_DURATION_MINUTES && duration_minutes <= MAX_DURATION_MINUTES,
+        (MIN_DURATION_MINUTES..=MAX_DURATION_MINUTES).contains(&duration_minutes),
         MvoteError::InvalidDuration
     );
 
+    // Ranked-choice polls are one ballot per wallet and need the ballots account
+    if voting_style == VotingStyle::RankedChoice {
+        require!(
+            vote_mode == VoteMode::FairVote,
+            MvoteError::RankedChoiceRequiresFairVote
+        );
+
+        let ranked_ballots = ctx
+            .accounts
+            .ranked_ballots
+            .as_mut()
+            .ok_or(MvoteError::RankedBallotsAccountRequired)?;
+        ranked_ballots.poll_id = config.total_polls_created;
+        ranked_ballots.ballots = Vec::new();
+        ranked_ballots.bump = ctx
+            .bumps
+            .ranked_ballots
+            .ok_or(MvoteError::RankedBallotsAccountRequired)?;
+    }
+
+    // Approval polls are one bitmask vote per wallet
+    if voting_style == VotingStyle::Approval {
+        require!(
+            vote_mode == VoteMode::FairVote,
+            MvoteError::ApprovalRequiresFairVote
+        );
+    }
+
+    // Scheduled polls may start up to max_start_lead_time in the future
+    let start_time = match start_time {
+        Some(start_time) => {
+            let latest_start = clock
+                .unix_timestamp
+                .checked_add(config.max_start_lead_time as i64)
+                .ok_or(MvoteError::Overflow)?;
+            require!(
+                (clock.unix_timestamp..=latest_start).contains(&start_time),
+                MvoteError::InvalidStartTime
+            );
+            start_time
+        }
+        None => clock.unix_timestamp,
+    };
+
+    // Validate pass rules
+    if let Some(pass_rules) = pass_rules {
+        require!(
+            (1..=BPS_DENOMINATOR as u16).contains(&pass_rules.pass_threshold_bps),
+            MvoteError::InvalidPassRules
+        );
+        require!(
+            !matches!(
+                pass_rules.quorum,
+                Some(Quorum::Votes(0)) | Some(Quorum::Tokens(0))
+            ),
+            MvoteError::InvalidPassRules
+        );
+    }
+
+    // Secret ballots hide a single option per wallet until the reveal window
+    if let Some(reveal_duration_minutes) = reveal_duration_minutes {
+        require!(
+            vote_mode == VoteMode::FairVote
+                && matches!(
+                    voting_style,
+                    VotingStyle::Binary | VotingStyle::MultipleChoice
+                ),
+            MvoteError::UnsupportedSecretBallot
+        );
+        require!(
+            (MIN_REVEAL_MINUTES..=MAX_REVEAL_MINUTES).contains(&reveal_duration_minutes),
+            MvoteError::InvalidRevealDuration
+        );
+    }
+
     // Check token balance
     require!(
         ctx.accounts.creator_token_account.amount >= config.poll_creation_cost,
@@ -112,8 +216,10 @@ pub fn handler(
 
     // Initialize poll
     let poll_id = config.total_polls_created;
-    let start_time = clock.unix_timestamp;
     let end_time = start_time + (duration_minutes as i64 * 60);
+    let reveal_end_time = reveal_duration_minutes
+        .map(|minutes| end_time + (minutes as i64 * 60))
+        .unwrap_or(0);
 
     poll.id = poll_id;
     poll.creator = ctx.accounts.creator.key();
@@ -126,7 +232,26 @@ pub fn handler(
     poll.end_time = end_time;
     poll.total_votes = 0;
     poll.total_tokens_spent = 0;
-    poll.is_active = true;
+    poll.status = if start_time > clock.unix_timestamp {
+        PollStatus::Scheduled
+    } else {
+        PollStatus::Active
+    };
+    poll.allow_vote_changes = false;
+    poll.is_tallied = false;
+    poll.winning_option = None;
+    poll.tally_rounds = Vec::new();
+    poll.is_secret = reveal_duration_minutes.is_some();
+    poll.reveal_end_time = reveal_end_time;
+    poll.committed_votes = 0;
+    poll.revealed_votes = 0;
+    poll.is_tie = false;
+    poll.finalized_at = 0;
+    poll.cancelled_at = 0;
+    poll.quorum = pass_rules.and_then(|rules| rules.quorum);
+    poll.pass_threshold_bps = pass_rules.map_or(0, |rules| rules.pass_threshold_bps);
+    poll.outcome = PollOutcome::Undecided;
+    poll.outstanding_records = 0;
     poll.bump = ctx.bumps.poll;
 
     // Update config
@@ -143,7 +268,28 @@ pub fn handler(
 
     msg!("Poll created with ID: {}", poll_id);
     msg!("Question: {}", poll.question);
+    msg!("Starts at: {}", start_time);
     msg!("Ends at: {}", end_time);
+    if poll.is_secret {
+        msg!("Reveals until: {}", reveal_end_time);
+    }
+
+    emit!(PollCreated {
+        poll_id,
+        poll: poll.key(),
+        creator: poll.creator,
+        question: poll.question.clone(),
+        options,
+        vote_mode,
+        voting_style,
+        status: poll.status,
+        start_time,
+        end_time,
+        is_secret: poll.is_secret,
+        reveal_end_time,
+        creation_cost: config.poll_creation_cost,
+        timestamp: clock.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::PollFinalized;
+use crate::state::{Poll, PollOutcome, PollStatus, Quorum, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct FinalizePoll<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+}
+
+pub fn handler(ctx: Context<FinalizePoll>, poll_id: u64) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Finalize only once voting is over
+    match poll.sync_status(current_time) {
+        PollStatus::ClosedEarly | PollStatus::Expired => {}
+        PollStatus::Finalized => return err!(MvoteError::AlreadyFinalized),
+        PollStatus::Cancelled => return err!(MvoteError::PollCancelled),
+        PollStatus::Scheduled | PollStatus::Active => return err!(MvoteError::PollStillActive),
+    }
+
+    // Secret ballots wait for the reveal window to close
+    if poll.is_secret {
+        require!(
+            current_time > poll.reveal_end_time,
+            MvoteError::RevealWindowOpen
+        );
+    }
+
+    if poll.voting_style == VotingStyle::RankedChoice {
+        // The instant-runoff winner is already stored by tally_ranked
+        require!(poll.is_tallied, MvoteError::RankedTallyRequired);
+        poll.is_tie = poll.winning_option.is_none() && poll.total_votes > 0;
+    } else {
+        let (winning_option, is_tie) = plurality_winner(&poll.vote_counts);
+        poll.winning_option = winning_option;
+        poll.is_tie = is_tie;
+    }
+
+    poll.outcome = evaluate_outcome(poll);
+
+    poll.status = PollStatus::Finalized;
+    poll.finalized_at = current_time;
+
+    match poll.winning_option {
+        Some(option) => msg!("Poll {} finalized, winner: option {}", poll_id, option),
+        None if poll.is_tie => msg!("Poll {} finalized, tied with no winner", poll_id),
+        None => msg!("Poll {} finalized with no votes", poll_id),
+    }
+    if poll.pass_threshold_bps > 0 {
+        msg!("Poll {} outcome: {:?}", poll_id, poll.outcome);
+    }
+
+    emit!(PollFinalized {
+        poll_id,
+        poll: poll.key(),
+        winning_option: poll.winning_option,
+        is_tie: poll.is_tie,
+        outcome: poll.outcome,
+        vote_counts: poll.vote_counts.clone(),
+        total_votes: poll.total_votes,
+        total_tokens_spent: poll.total_tokens_spent,
+        unrevealed_votes: poll.unrevealed_votes(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Option with the highest vote count.
+///
+/// Tie policy: if the highest count is shared by two or more options there is
+/// no winner and `is_tie` is true. A poll with no votes has no winner and is
+/// not a tie.
+fn plurality_winner(vote_counts: &[u64]) -> (Option<u8>, bool) {
+    let highest = vote_counts.iter().copied().max().unwrap_or(0);
+    if highest == 0 {
+        return (None, false);
+    }
+
+    let mut leaders = vote_counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count == highest)
+        .map(|(option, _)| option as u8);
+
+    match (leaders.next(), leaders.next()) {
+        (Some(option), None) => (Some(option), false),
+        _ => (None, true),
+    }
+}
+
+/// Apply the poll's quorum and pass threshold.
+///
+/// The winner's share is measured against the sum of `vote_counts` (the
+/// final instant-runoff round for ranked-choice polls). Approval ballots can
+/// count towards several options, so there the share is of all voters. A
+/// poll without a winner fails once the quorum is met.
+fn evaluate_outcome(poll: &Poll) -> PollOutcome {
+    if poll.pass_threshold_bps == 0 {
+        return PollOutcome::Undecided;
+    }
+
+    let quorum_met = match poll.quorum {
+        Some(Quorum::Votes(minimum)) => poll.total_votes >= minimum,
+        Some(Quorum::Tokens(minimum)) => poll.total_tokens_spent >= minimum,
+        None => true,
+    };
+    if !quorum_met {
+        return PollOutcome::QuorumNotMet;
+    }
+
+    let counts: &[u64] = if poll.voting_style == VotingStyle::RankedChoice {
+        poll.tally_rounds.last().map_or(&[], |round| &round[..])
+    } else {
+        &poll.vote_counts
+    };
+    let total: u128 = if poll.voting_style == VotingStyle::Approval {
+        poll.total_votes as u128
+    } else {
+        counts.iter().map(|count| *count as u128).sum()
+    };
+
+    let passed = match poll.winning_option {
+        Some(option) => {
+            counts[option as usize] as u128 * BPS_DENOMINATOR as u128
+                >= poll.pass_threshold_bps as u128 * total
+        }
+        None => false,
+    };
+
+    if passed {
+        PollOutcome::Passed
+    } else {
+        PollOutcome::Failed
+    }
+}
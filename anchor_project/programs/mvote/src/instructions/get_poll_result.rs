@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::{Poll, PollResult};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct GetPollResult<'info> {
+    #[account(
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+}
+
+pub fn handler(ctx: Context<GetPollResult>, _poll_id: u64) -> Result<PollResult> {
+    let poll = &ctx.accounts.poll;
+    let clock = Clock::get()?;
+
+    // Report the time-based status even if no instruction has stored it yet
+    let mut result = PollResult::from(&**poll);
+    result.status = poll.current_status(clock.unix_timestamp);
+
+    Ok(result)
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 
 use crate::constants::*;
+use crate::events::Initialized;
 use crate::state::Config;
 
 #[derive(Accounts)]
@@ -53,11 +54,30 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     config.poll_creation_cost = POLL_CREATION_COST;
     config.vote_cost = VOTE_COST;
     config.total_polls_created = 0;
+    config.pending_admin = Pubkey::default();
+    config.treasury = ctx.accounts.admin.key();
+    config.paused = false;
+    config.price_oracle = Pubkey::default();
+    config.price_feed_id = [0; 32];
+    config.max_price_age = DEFAULT_MAX_PRICE_AGE;
+    config.max_price_conf_bps = DEFAULT_MAX_PRICE_CONF_BPS;
+    config.redeem_spread_bps = REDEEM_SPREAD_BPS;
+    config.daily_redeem_limit = DAILY_REDEEM_LIMIT;
+    config.max_start_lead_time = DEFAULT_MAX_START_LEAD_TIME;
+    config.poll_retention_period = DEFAULT_POLL_RETENTION_PERIOD;
     config.bump = ctx.bumps.config;
 
     msg!("mVote program initialized!");
     msg!("Token Mint: {}", ctx.accounts.token_mint.key());
     msg!("SOL Vault: {}", ctx.accounts.sol_vault.key());
 
+    emit!(Initialized {
+        admin: ctx.accounts.admin.key(),
+        config: config.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        sol_vault: ctx.accounts.sol_vault.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::ConfigMigrated;
+use crate::migration;
+use crate::state::Config;
+
+/// Config layout before admin transfer, treasury, pause, oracle, redemption
+/// and poll scheduling settings were added
+#[derive(AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub admin: Pubkey,
+    pub token_mint: Pubkey,
+    pub sol_vault: Pubkey,
+    pub sol_usd_rate: u64,
+    pub tokens_per_usd: u64,
+    pub daily_purchase_limit: u64,
+    pub poll_creation_cost: u64,
+    pub vote_cost: u64,
+    pub total_polls_created: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Must be the admin recorded in the legacy config, pays the extra rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Legacy config account, deserialized and checked in the handler
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+
+    let legacy: LegacyConfig =
+        migration::read_legacy(&config_info, Config::DISCRIMINATOR, Config::SIZE)?;
+    require!(
+        legacy.admin == ctx.accounts.admin.key(),
+        MvoteError::UnauthorizedAdmin
+    );
+
+    // Existing settings are kept, new ones get the same defaults as initialize
+    let config = Config {
+        admin: legacy.admin,
+        token_mint: legacy.token_mint,
+        sol_vault: legacy.sol_vault,
+        sol_usd_rate: legacy.sol_usd_rate,
+        tokens_per_usd: legacy.tokens_per_usd,
+        daily_purchase_limit: legacy.daily_purchase_limit,
+        poll_creation_cost: legacy.poll_creation_cost,
+        vote_cost: legacy.vote_cost,
+        total_polls_created: legacy.total_polls_created,
+        pending_admin: Pubkey::default(),
+        treasury: legacy.admin,
+        paused: false,
+        price_oracle: Pubkey::default(),
+        price_feed_id: [0; 32],
+        max_price_age: DEFAULT_MAX_PRICE_AGE,
+        max_price_conf_bps: DEFAULT_MAX_PRICE_CONF_BPS,
+        redeem_spread_bps: REDEEM_SPREAD_BPS,
+        daily_redeem_limit: DAILY_REDEEM_LIMIT,
+        max_start_lead_time: DEFAULT_MAX_START_LEAD_TIME,
+        poll_retention_period: DEFAULT_POLL_RETENTION_PERIOD,
+        bump: legacy.bump,
+    };
+
+    migration::rewrite(
+        &config_info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Config::SIZE,
+        &config,
+    )?;
+
+    msg!("Config migrated by admin {}", ctx.accounts.admin.key());
+
+    emit!(ConfigMigrated {
+        admin: ctx.accounts.admin.key(),
+        config: config_info.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
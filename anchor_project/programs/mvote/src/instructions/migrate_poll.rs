@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::events::PollMigrated;
+use crate::migration;
+use crate::state::{Poll, PollOutcome, PollStatus, VoteMode, VotingStyle};
+
+/// Poll layout before `status` replaced `is_active`
+#[derive(AnchorDeserialize)]
+pub struct LegacyPoll {
+    pub id: u64,
+    pub creator: Pubkey,
+    pub question: String,
+    pub options: Vec<String>,
+    pub vote_counts: Vec<u64>,
+    pub vote_mode: VoteMode,
+    pub voting_style: VotingStyle,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_votes: u64,
+    pub total_tokens_spent: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct MigratePoll<'info> {
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy poll account, deserialized manually in the handler
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub poll: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigratePoll>, poll_id: u64) -> Result<()> {
+    let poll_info = ctx.accounts.poll.to_account_info();
+    let clock = Clock::get()?;
+
+    // Only legacy accounts (smaller than the current layout) are migrated
+    let legacy: LegacyPoll = migration::read_legacy(&poll_info, Poll::DISCRIMINATOR, Poll::SIZE)?;
+
+    let mut poll = Poll {
+        id: legacy.id,
+        creator: legacy.creator,
+        question: legacy.question,
+        options: legacy.options,
+        vote_counts: legacy.vote_counts,
+        vote_mode: legacy.vote_mode,
+        voting_style: legacy.voting_style,
+        start_time: legacy.start_time,
+        end_time: legacy.end_time,
+        total_votes: legacy.total_votes,
+        total_tokens_spent: legacy.total_tokens_spent,
+        status: if legacy.is_active {
+            PollStatus::Active
+        } else {
+            PollStatus::ClosedEarly
+        },
+        // Legacy vote records predate vote changes
+        allow_vote_changes: false,
+        is_tallied: false,
+        winning_option: None,
+        tally_rounds: Vec::new(),
+        is_secret: false,
+        reveal_end_time: 0,
+        committed_votes: 0,
+        revealed_votes: 0,
+        is_tie: false,
+        finalized_at: 0,
+        cancelled_at: 0,
+        quorum: None,
+        pass_threshold_bps: 0,
+        outcome: PollOutcome::Undecided,
+        // Every legacy vote created one record and none were refunded
+        outstanding_records: legacy.total_votes,
+        bump: legacy.bump,
+    };
+    poll.sync_status(clock.unix_timestamp);
+
+    migration::rewrite(
+        &poll_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Poll::SIZE,
+        &poll,
+    )?;
+
+    msg!("Poll {} migrated with status {:?}", poll_id, poll.status);
+
+    emit!(PollMigrated {
+        poll_id,
+        poll: poll_info.key(),
+        status: poll.status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
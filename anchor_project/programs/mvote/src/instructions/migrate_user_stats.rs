@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::events::UserStatsMigrated;
+use crate::migration;
+use crate::state::UserStats;
+
+/// UserStats layout before redemption tracking was added
+#[derive(AnchorDeserialize)]
+pub struct LegacyUserStats {
+    pub wallet: Pubkey,
+    pub tokens_purchased_today: u64,
+    pub last_purchase_day: i64,
+    pub total_tokens_purchased: u64,
+    pub polls_created: u64,
+    pub votes_cast: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct MigrateUserStats<'info> {
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy user stats account, deserialized manually in the handler
+    #[account(
+        mut,
+        seeds = [USER_SEED, wallet.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub user_stats: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateUserStats>, wallet: Pubkey) -> Result<()> {
+    let user_stats_info = ctx.accounts.user_stats.to_account_info();
+
+    let legacy: LegacyUserStats =
+        migration::read_legacy(&user_stats_info, UserStats::DISCRIMINATOR, UserStats::SIZE)?;
+
+    let user_stats = UserStats {
+        wallet: legacy.wallet,
+        tokens_purchased_today: legacy.tokens_purchased_today,
+        last_purchase_day: legacy.last_purchase_day,
+        total_tokens_purchased: legacy.total_tokens_purchased,
+        polls_created: legacy.polls_created,
+        votes_cast: legacy.votes_cast,
+        tokens_redeemed_today: 0,
+        last_redeem_day: 0,
+        total_tokens_redeemed: 0,
+        bump: legacy.bump,
+    };
+
+    migration::rewrite(
+        &user_stats_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        UserStats::SIZE,
+        &user_stats,
+    )?;
+
+    msg!("User stats for {} migrated", wallet);
+
+    emit!(UserStatsMigrated {
+        wallet,
+        user_stats: user_stats_info.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
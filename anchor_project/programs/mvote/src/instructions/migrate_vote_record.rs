@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::events::VoteRecordMigrated;
+use crate::migration;
+use crate::state::VoteRecord;
+
+/// VoteRecord layout before vote changes, escrow and alternative ballots
+#[derive(AnchorDeserialize)]
+pub struct LegacyVoteRecord {
+    pub voter: Pubkey,
+    pub poll_id: u64,
+    pub option_index: u8,
+    pub tokens_spent: u64,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64, voter: Pubkey)]
+pub struct MigrateVoteRecord<'info> {
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Legacy vote record, deserialized manually in the handler
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub vote_record: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateVoteRecord>, poll_id: u64, voter: Pubkey) -> Result<()> {
+    let vote_record_info = ctx.accounts.vote_record.to_account_info();
+
+    let legacy: LegacyVoteRecord = migration::read_legacy(
+        &vote_record_info,
+        VoteRecord::DISCRIMINATOR,
+        VoteRecord::SIZE,
+    )?;
+
+    // Legacy records hold a single vote on a single option
+    let mut option_tokens = [0u64; 4];
+    option_tokens[legacy.option_index as usize] = legacy.tokens_spent;
+
+    let vote_record = VoteRecord {
+        voter: legacy.voter,
+        poll_id: legacy.poll_id,
+        option_index: legacy.option_index,
+        tokens_spent: legacy.tokens_spent,
+        voted_at: legacy.voted_at,
+        vote_count: 1,
+        option_tokens,
+        tokens_reclaimed: false,
+        rankings: Vec::new(),
+        option_mask: 0,
+        commitment: [0; 32],
+        is_revealed: false,
+        bump: legacy.bump,
+    };
+
+    migration::rewrite(
+        &vote_record_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VoteRecord::SIZE,
+        &vote_record,
+    )?;
+
+    msg!("Vote record for poll {} by {} migrated", poll_id, voter);
+
+    emit!(VoteRecordMigrated {
+        poll_id,
+        voter,
+        vote_record: vote_record_info.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,9 +3,59 @@ pub mod purchase_tokens;
 pub mod create_poll;
 pub mod vote;
 pub mod close_poll;
+pub mod update_config;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod withdraw_vault;
+pub mod set_pause;
+pub mod redeem_tokens;
+pub mod change_vote;
+pub mod retract_vote;
+pub mod set_vote_changes;
+pub mod reclaim_vote_tokens;
+pub mod vote_ranked;
+pub mod tally_ranked;
+pub mod vote_approval;
+pub mod commit_vote;
+pub mod reveal_vote;
+pub mod finalize_poll;
+pub mod get_poll_result;
+pub mod migrate_poll;
+pub mod cancel_poll;
+pub mod claim_refund;
+pub mod close_vote_record;
+pub mod close_poll_account;
+pub mod migrate_config;
+pub mod migrate_user_stats;
+pub mod migrate_vote_record;
 
 pub use initialize::*;
 pub use purchase_tokens::*;
 pub use create_poll::*;
 pub use vote::*;
 pub use close_poll::*;
+pub use update_config::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use withdraw_vault::*;
+pub use set_pause::*;
+pub use redeem_tokens::*;
+pub use change_vote::*;
+pub use retract_vote::*;
+pub use set_vote_changes::*;
+pub use reclaim_vote_tokens::*;
+pub use vote_ranked::*;
+pub use tally_ranked::*;
+pub use vote_approval::*;
+pub use commit_vote::*;
+pub use reveal_vote::*;
+pub use finalize_poll::*;
+pub use get_poll_result::*;
+pub use migrate_poll::*;
+pub use cancel_poll::*;
+pub use claim_refund::*;
+pub use close_vote_record::*;
+pub use close_poll_account::*;
+pub use migrate_config::*;
+pub use migrate_user_stats::*;
+pub use migrate_vote_record::*;
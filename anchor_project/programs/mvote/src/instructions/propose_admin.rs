@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::AdminProposed;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ MvoteError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // Proposing the default pubkey cancels any pending transfer
+    config.pending_admin = new_admin;
+
+    if new_admin == Pubkey::default() {
+        msg!("Pending admin transfer cancelled");
+    } else {
+        msg!("Admin transfer proposed: {} -> {}", config.admin, new_admin);
+    }
+
+    emit!(AdminProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
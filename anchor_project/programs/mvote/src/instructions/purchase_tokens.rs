@@ -4,6 +4,8 @@ use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
 use crate::constants::*;
 use crate::errors::MvoteError;
+use crate::events::TokensPurchased;
+use crate::oracle;
 use crate::state::{Config, UserStats};
 
 #[derive(Accounts)]
@@ -50,11 +52,17 @@ pub struct PurchaseTokens<'info> {
     )]
     pub sol_vault: SystemAccount<'info>,
 
+    /// CHECK: Price feed, validated against config and parsed in oracle::get_sol_usd_rate
+    #[account(
+        constraint = price_oracle.key() == config.price_oracle @ MvoteError::InvalidOracleAccount
+    )]
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<PurchaseTokens>, amount: u64, max_lamports: Option<u64>) -> Result<()> {
     require!(amount > 0, MvoteError::InvalidAmount);
 
     let config = &ctx.accounts.config;
@@ -62,6 +70,8 @@ pub fn handler(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
+    require!(!config.paused, MvoteError::ProgramPaused);
+
     // Initialize user stats if new
     if user_stats.wallet == Pubkey::default() {
         user_stats.wallet = ctx.accounts.buyer.key();
@@ -70,6 +80,9 @@ pub fn handler(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
         user_stats.total_tokens_purchased = 0;
         user_stats.polls_created = 0;
         user_stats.votes_cast = 0;
+        user_stats.tokens_redeemed_today = 0;
+        user_stats.last_redeem_day = 0;
+        user_stats.total_tokens_redeemed = 0;
         user_stats.bump = ctx.bumps.user_stats;
     }
 
@@ -89,11 +102,20 @@ pub fn handler(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
         MvoteError::DailyLimitExceeded
     );
 
+    // Use the oracle price if one is configured, otherwise the fixed rate
+    let sol_usd_rate =
+        oracle::current_sol_usd_rate(config, ctx.accounts.price_oracle.as_deref(), current_time)?;
+
     // Calculate SOL cost
     // amount is in token smallest units (6 decimals)
     // sol_usd_rate is price in cents (e.g., 15000 = $150.00)
-    // 1 USD = 1 mVote = 1_000_000 units
-    let sol_cost = calculate_sol_cost(amount, config.sol_usd_rate)?;
+    // tokens_per_usd is token units per USD (1_000_000 = 1 mVote per USD)
+    let sol_cost = calculate_sol_cost(amount, sol_usd_rate, config.tokens_per_usd)?;
+
+    // Check slippage against the buyer's limit
+    if let Some(max_lamports) = max_lamports {
+        require!(sol_cost <= max_lamports, MvoteError::SlippageExceeded);
+    }
 
     // Transfer SOL from buyer to vault
     system_program::transfer(
@@ -132,16 +154,27 @@ pub fn handler(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
         .ok_or(MvoteError::Overflow)?;
 
     msg!(
-        "Purchased {} mVote tokens for {} lamports",
+        "Purchased {} mVote tokens for {} lamports (rate {})",
         amount,
-        sol_cost
+        sol_cost,
+        sol_usd_rate
     );
 
+    emit!(TokensPurchased {
+        buyer: ctx.accounts.buyer.key(),
+        buyer_token_account: ctx.accounts.buyer_token_account.key(),
+        amount,
+        sol_cost,
+        sol_usd_rate,
+        tokens_purchased_today: new_total,
+        timestamp: current_time,
+    });
+
     Ok(())
 }
 
 /// Check if it's a new day in SGT (UTC+8)
-fn is_new_day_sgt(last_timestamp: i64, current_timestamp: i64) -> bool {
+pub fn is_new_day_sgt(last_timestamp: i64, current_timestamp: i64) -> bool {
     if last_timestamp == 0 {
         return true;
     }
@@ -153,25 +186,28 @@ fn is_new_day_sgt(last_timestamp: i64, current_timestamp: i64) -> bool {
 }
 
 /// Calculate SOL cost in lamports for given token amount
-fn calculate_sol_cost(token_amount: u64, sol_usd_rate: u64) -> Result<u64> {
-    // token_amount is in smallest units (6 decimals), so 1_000_000 = 1 mVote = $1
+pub fn calculate_sol_cost(
+    token_amount: u64,
+    sol_usd_rate: u64,
+    tokens_per_usd: u64,
+) -> Result<u64> {
+    // token_amount is in smallest units (6 decimals), tokens_per_usd units buy $1
     // sol_usd_rate is in cents, so 15000 = $150.00
     // LAMPORTS_PER_SOL = 1_000_000_000
 
-    // USD cost = token_amount / 1_000_000
+    // USD cost = token_amount / tokens_per_usd
     // SOL cost = USD_cost / (sol_usd_rate / 100)
     // lamports = SOL_cost * 1_000_000_000
 
-    // Simplified: lamports = (token_amount * 1_000_000_000 * 100) / (1_000_000 * sol_usd_rate)
-    // = (token_amount * 100_000) / sol_usd_rate
+    // Simplified: lamports = (token_amount * 1_000_000_000 * 100) / (tokens_per_usd * sol_usd_rate)
 
     let lamports = (token_amount as u128)
         .checked_mul(100_000_000_000)
         .ok_or(MvoteError::Overflow)?
         .checked_div(sol_usd_rate as u128)
         .ok_or(MvoteError::Overflow)?
-        .checked_div(1_000_000)
-        .ok_or(MvoteError::Overflow)? as u64;
+        .checked_div(tokens_per_usd as u128)
+        .ok_or(MvoteError::Overflow)?;
 
-    Ok(lamports)
+    u64::try_from(lamports).map_err(|_| MvoteError::Overflow.into())
 }
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::EscrowReclaimed;
+use crate::state::{Config, Poll, VoteMode, VoteRecord};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct ReclaimVoteTokens<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, poll_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = poll
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ReclaimVoteTokens>, poll_id: u64) -> Result<()> {
+    let poll = &ctx.accounts.poll;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        poll.vote_mode == VoteMode::EscrowVote,
+        MvoteError::NotEscrowPoll
+    );
+
+    // Tokens unlock once the poll has ended or been closed
+    require!(
+        poll.current_status(current_time).is_voting_over(),
+        MvoteError::PollStillActive
+    );
+
+    require!(
+        !vote_record.tokens_reclaimed,
+        MvoteError::TokensAlreadyReclaimed
+    );
+
+    // Return escrowed tokens, signed by the poll PDA
+    let poll_id_bytes = poll_id.to_le_bytes();
+    let poll_seeds = &[POLL_SEED, poll_id_bytes.as_ref(), &[poll.bump]];
+    let signer_seeds = &[&poll_seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.poll.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        vote_record.tokens_spent,
+    )?;
+
+    vote_record.tokens_reclaimed = true;
+
+    let poll = &mut ctx.accounts.poll;
+    poll.outstanding_records = poll
+        .outstanding_records
+        .checked_sub(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Reclaimed {} escrowed tokens from poll {}",
+        vote_record.tokens_spent,
+        poll_id
+    );
+
+    emit!(EscrowReclaimed {
+        poll_id,
+        voter: vote_record.voter,
+        amount: vote_record.tokens_spent,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
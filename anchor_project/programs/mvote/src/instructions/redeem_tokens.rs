@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::TokensRedeemed;
+use crate::instructions::purchase_tokens::{calculate_sol_cost, is_new_day_sgt};
+use crate::oracle;
+use crate::state::{Config, UserStats};
+
+#[derive(Accounts)]
+pub struct RedeemTokens<'info> {
+    #[account(mut)]
+    pub redeemer: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, redeemer.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = redeemer
+    )]
+    pub redeemer_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: Price feed, validated against config and parsed in oracle::get_sol_usd_rate
+    #[account(
+        constraint = price_oracle.key() == config.price_oracle @ MvoteError::InvalidOracleAccount
+    )]
+    pub price_oracle: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, MvoteError::InvalidAmount);
+
+    let config = &ctx.accounts.config;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(!config.paused, MvoteError::ProgramPaused);
+
+    // Check if it's a new day in SGT and reset daily redemption limit
+    if is_new_day_sgt(user_stats.last_redeem_day, current_time) {
+        user_stats.tokens_redeemed_today = 0;
+        user_stats.last_redeem_day = current_time;
+    }
+
+    // Check daily redemption limit
+    let new_total = user_stats
+        .tokens_redeemed_today
+        .checked_add(amount)
+        .ok_or(MvoteError::Overflow)?;
+    require!(
+        new_total <= config.daily_redeem_limit,
+        MvoteError::DailyRedeemLimitExceeded
+    );
+
+    // Check token balance
+    require!(
+        ctx.accounts.redeemer_token_account.amount >= amount,
+        MvoteError::InsufficientTokens
+    );
+
+    // Calculate SOL payout at the current rate minus the spread
+    let sol_usd_rate =
+        oracle::current_sol_usd_rate(config, ctx.accounts.price_oracle.as_deref(), current_time)?;
+    let gross_lamports = calculate_sol_cost(amount, sol_usd_rate, config.tokens_per_usd)?;
+    let payout = (gross_lamports as u128)
+        .checked_mul(BPS_DENOMINATOR.saturating_sub(config.redeem_spread_bps as u64) as u128)
+        .ok_or(MvoteError::Overflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(MvoteError::Overflow)? as u64;
+    require!(payout > 0, MvoteError::InvalidAmount);
+
+    // Vault must stay rent-exempt after the payout
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let available = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(rent_exempt_minimum);
+    require!(payout <= available, MvoteError::InsufficientVaultBalance);
+
+    // Burn redeemed tokens
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.redeemer_token_account.to_account_info(),
+                authority: ctx.accounts.redeemer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Pay SOL from vault to redeemer
+    let vault_seeds = &[VAULT_SEED, &[ctx.bumps.sol_vault]];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: ctx.accounts.redeemer.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        payout,
+    )?;
+
+    // Update user stats
+    user_stats.tokens_redeemed_today = new_total;
+    user_stats.total_tokens_redeemed = user_stats
+        .total_tokens_redeemed
+        .checked_add(amount)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Redeemed {} mVote tokens for {} lamports (rate {}, spread {} bps)",
+        amount,
+        payout,
+        sol_usd_rate,
+        config.redeem_spread_bps
+    );
+
+    emit!(TokensRedeemed {
+        redeemer: ctx.accounts.redeemer.key(),
+        amount,
+        payout,
+        sol_usd_rate,
+        redeem_spread_bps: config.redeem_spread_bps,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteRetracted;
+use crate::state::{Config, Poll, UserStats, VoteMode, VoteRecord, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct RetractVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, voter.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+pub fn handler(ctx: Context<RetractVote>, poll_id: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let poll = &mut ctx.accounts.poll;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let vote_record = &ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(!config.paused, MvoteError::ProgramPaused);
+
+    // Check poll is active and accepts vote changes
+    poll.require_voting_open(current_time)?;
+    require!(poll.allow_vote_changes, MvoteError::VoteChangesDisabled);
+
+    // Ranked ballots are aggregated separately and cannot be edited
+    require!(
+        poll.voting_style != VotingStyle::RankedChoice,
+        MvoteError::VoteChangesDisabled
+    );
+
+    // Secret ballots are fixed once committed
+    require!(!poll.is_secret, MvoteError::VoteChangesDisabled);
+
+    // Closing the record would strand escrowed tokens
+    require!(
+        poll.vote_mode != VoteMode::EscrowVote,
+        MvoteError::EscrowRetractNotAllowed
+    );
+
+    // Remove the record's weight from the tally (burned tokens are not refunded)
+    for option in 0..poll.options.len() {
+        let weight = vote_record.weight_for(poll.vote_mode, option);
+        poll.vote_counts[option] = poll.vote_counts[option]
+            .checked_sub(weight)
+            .ok_or(MvoteError::Overflow)?;
+    }
+
+    poll.total_votes = poll
+        .total_votes
+        .checked_sub(vote_record.vote_count as u64)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.total_tokens_spent = poll
+        .total_tokens_spent
+        .checked_sub(vote_record.tokens_spent)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.outstanding_records = poll
+        .outstanding_records
+        .checked_sub(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    // Update user stats
+    user_stats.votes_cast = user_stats
+        .votes_cast
+        .checked_sub(vote_record.vote_count as u64)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Vote on poll {} retracted by {}",
+        poll_id,
+        vote_record.voter
+    );
+
+    emit!(VoteRetracted {
+        poll_id,
+        voter: vote_record.voter,
+        tokens_spent: vote_record.tokens_spent,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteCast;
+use crate::state::{Poll, PollStatus, VoteRecord};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct RevealVote<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+}
+
+pub fn handler(
+    ctx: Context<RevealVote>,
+    poll_id: u64,
+    option_index: u8,
+    salt: [u8; 32],
+) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(poll.is_secret, MvoteError::NotSecretPoll);
+
+    // Reveals open once voting is over and close at reveal_end_time
+    require!(
+        matches!(
+            poll.sync_status(current_time),
+            PollStatus::ClosedEarly | PollStatus::Expired
+        ) && current_time <= poll.reveal_end_time,
+        MvoteError::RevealWindowClosed
+    );
+    require!(!vote_record.is_revealed, MvoteError::AlreadyRevealed);
+
+    // Check valid option
+    require!(
+        (option_index as usize) < poll.options.len(),
+        MvoteError::InvalidOption
+    );
+
+    // Commitment binds the option to the voter so it cannot be copied
+    let expected = hashv(&[&[option_index], &salt, ctx.accounts.voter.key().as_ref()]);
+    require!(
+        expected.to_bytes() == vote_record.commitment,
+        MvoteError::CommitmentMismatch
+    );
+
+    // Add the revealed vote to the tally
+    poll.vote_counts[option_index as usize] = poll.vote_counts[option_index as usize]
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.total_votes = poll
+        .total_votes
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.revealed_votes = poll
+        .revealed_votes
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    vote_record.option_index = option_index;
+    vote_record.option_tokens[option_index as usize] = vote_record.tokens_spent;
+    vote_record.vote_count = 1;
+    vote_record.is_revealed = true;
+
+    msg!(
+        "Vote on poll {} revealed for option {} ({} of {} commitments revealed)",
+        poll_id,
+        option_index,
+        poll.revealed_votes,
+        poll.committed_votes
+    );
+
+    emit!(VoteCast {
+        poll_id,
+        poll: poll.key(),
+        voter: ctx.accounts.voter.key(),
+        vote_record: vote_record.key(),
+        option_index,
+        option_mask: 1 << option_index,
+        tokens_spent: 0,
+        vote_weight: 1,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
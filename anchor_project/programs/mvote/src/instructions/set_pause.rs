@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::PauseSet;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ MvoteError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    msg!("paused: {} -> {}", config.paused, paused);
+    config.paused = paused;
+
+    emit!(PauseSet {
+        admin: ctx.accounts.admin.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
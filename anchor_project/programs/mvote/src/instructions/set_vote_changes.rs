@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteChangesSet;
+use crate::state::{Poll, PollStatus};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct SetVoteChanges<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump,
+        constraint = poll.creator == creator.key() @ MvoteError::Unauthorized
+    )]
+    pub poll: Account<'info, Poll>,
+}
+
+pub fn handler(ctx: Context<SetVoteChanges>, _poll_id: u64, allowed: bool) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let clock = Clock::get()?;
+
+    // Only polls that can still take votes
+    require!(
+        matches!(
+            poll.sync_status(clock.unix_timestamp),
+            PollStatus::Scheduled | PollStatus::Active
+        ),
+        MvoteError::PollNotActive
+    );
+
+    poll.allow_vote_changes = allowed;
+
+    msg!("Vote changes on poll {} set to {}", poll.id, allowed);
+
+    emit!(VoteChangesSet {
+        poll_id: poll.id,
+        allowed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::RankedTallied;
+use crate::state::{BallotCount, Poll, RankedBallots, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct TallyRanked<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        seeds = [BALLOTS_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = ranked_ballots.bump
+    )]
+    pub ranked_ballots: Account<'info, RankedBallots>,
+}
+
+pub fn handler(ctx: Context<TallyRanked>, poll_id: u64) -> Result<()> {
+    let poll = &mut ctx.accounts.poll;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        poll.voting_style == VotingStyle::RankedChoice,
+        MvoteError::NotRankedPoll
+    );
+
+    // Tally only once voting is over
+    require!(
+        poll.sync_status(current_time).is_voting_over(),
+        MvoteError::PollStillActive
+    );
+    require!(!poll.is_tallied, MvoteError::AlreadyTallied);
+
+    let (winning_option, tally_rounds) =
+        instant_runoff(&ctx.accounts.ranked_ballots.ballots, poll.options.len())?;
+
+    poll.winning_option = winning_option;
+    poll.tally_rounds = tally_rounds;
+    poll.is_tallied = true;
+
+    match winning_option {
+        Some(option) => msg!(
+            "Poll {} tallied after {} rounds, winner: option {}",
+            poll_id,
+            poll.tally_rounds.len(),
+            option
+        ),
+        None => msg!(
+            "Poll {} tallied after {} rounds, no winner",
+            poll_id,
+            poll.tally_rounds.len()
+        ),
+    }
+
+    emit!(RankedTallied {
+        poll_id,
+        winning_option,
+        tally_rounds: poll.tally_rounds.clone(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Instant-runoff elimination over aggregated ballots.
+///
+/// Each round counts every ballot for its highest-ranked remaining option.
+/// An option with more than half of the non-exhausted ballots wins. Otherwise
+/// all options tied for the fewest votes are eliminated together. If every
+/// remaining option is tied, or no ballots remain, there is no winner.
+fn instant_runoff(
+    ballots: &[BallotCount],
+    option_count: usize,
+) -> Result<(Option<u8>, Vec<[u64; 4]>)> {
+    let mut remaining = [false; MAX_OPTIONS];
+    remaining[..option_count].fill(true);
+    let mut rounds: Vec<[u64; 4]> = Vec::new();
+
+    loop {
+        // Count each ballot for its top remaining option
+        let mut counts = [0u64; 4];
+        for ballot in ballots {
+            if let Some(option) = ballot
+                .rankings
+                .iter()
+                .map(|option| *option as usize)
+                .find(|option| remaining[*option])
+            {
+                counts[option] = counts[option]
+                    .checked_add(ballot.count)
+                    .ok_or(MvoteError::Overflow)?;
+            }
+        }
+        rounds.push(counts);
+
+        let active_total = counts
+            .iter()
+            .try_fold(0u64, |total, count| total.checked_add(*count))
+            .ok_or(MvoteError::Overflow)?;
+        if active_total == 0 {
+            return Ok((None, rounds));
+        }
+
+        // Majority of non-exhausted ballots wins
+        if let Some(option) = (0..option_count)
+            .find(|option| remaining[*option] && counts[*option] as u128 * 2 > active_total as u128)
+        {
+            return Ok((Some(option as u8), rounds));
+        }
+
+        // Eliminate every option tied for last place
+        let lowest = (0..option_count)
+            .filter(|option| remaining[*option])
+            .map(|option| counts[option])
+            .min()
+            .unwrap_or(0);
+        let survivors = (0..option_count)
+            .filter(|option| remaining[*option] && counts[*option] > lowest)
+            .count();
+        if survivors == 0 || rounds.len() == MAX_TALLY_ROUNDS {
+            return Ok((None, rounds));
+        }
+
+        for option in 0..option_count {
+            if counts[option] == lowest {
+                remaining[option] = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::ConfigUpdated;
+use crate::state::Config;
+
+/// Config fields to update. `None` leaves the current value untouched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams {
+    pub sol_usd_rate: Option<u64>,
+    pub tokens_per_usd: Option<u64>,
+    pub daily_purchase_limit: Option<u64>,
+    pub poll_creation_cost: Option<u64>,
+    pub vote_cost: Option<u64>,
+    pub treasury: Option<Pubkey>,
+    /// Set to the default pubkey to fall back to the fixed sol_usd_rate.
+    /// Setting an oracle requires `price_feed_id` in the same update.
+    pub price_oracle: Option<Pubkey>,
+    /// Pyth feed id expected in the oracle account (e.g. SOL/USD)
+    pub price_feed_id: Option<[u8; 32]>,
+    pub max_price_age: Option<u64>,
+    pub max_price_conf_bps: Option<u16>,
+    pub redeem_spread_bps: Option<u16>,
+    /// Set to 0 to disable redemptions
+    pub daily_redeem_limit: Option<u64>,
+    /// Set to 0 to disable scheduled polls
+    pub max_start_lead_time: Option<u64>,
+    pub poll_retention_period: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ MvoteError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(sol_usd_rate) = params.sol_usd_rate {
+        require!(
+            (MIN_SOL_USD_RATE..=MAX_SOL_USD_RATE).contains(&sol_usd_rate),
+            MvoteError::InvalidConfigValue
+        );
+        msg!("sol_usd_rate: {} -> {}", config.sol_usd_rate, sol_usd_rate);
+        config.sol_usd_rate = sol_usd_rate;
+    }
+
+    if let Some(tokens_per_usd) = params.tokens_per_usd {
+        require!(
+            (1..=MAX_TOKENS_PER_USD).contains(&tokens_per_usd),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "tokens_per_usd: {} -> {}",
+            config.tokens_per_usd,
+            tokens_per_usd
+        );
+        config.tokens_per_usd = tokens_per_usd;
+    }
+
+    if let Some(daily_purchase_limit) = params.daily_purchase_limit {
+        require!(
+            (1..=MAX_DAILY_PURCHASE_LIMIT).contains(&daily_purchase_limit),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "daily_purchase_limit: {} -> {}",
+            config.daily_purchase_limit,
+            daily_purchase_limit
+        );
+        config.daily_purchase_limit = daily_purchase_limit;
+    }
+
+    if let Some(poll_creation_cost) = params.poll_creation_cost {
+        require!(
+            (1..=MAX_POLL_CREATION_COST).contains(&poll_creation_cost),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "poll_creation_cost: {} -> {}",
+            config.poll_creation_cost,
+            poll_creation_cost
+        );
+        config.poll_creation_cost = poll_creation_cost;
+    }
+
+    if let Some(vote_cost) = params.vote_cost {
+        require!(
+            (1..=MAX_VOTE_COST).contains(&vote_cost),
+            MvoteError::InvalidConfigValue
+        );
+        msg!("vote_cost: {} -> {}", config.vote_cost, vote_cost);
+        config.vote_cost = vote_cost;
+    }
+
+    if let Some(treasury) = params.treasury {
+        require!(
+            treasury != Pubkey::default(),
+            MvoteError::InvalidConfigValue
+        );
+        msg!("treasury: {} -> {}", config.treasury, treasury);
+        config.treasury = treasury;
+    }
+
+    if let Some(price_oracle) = params.price_oracle {
+        // A new oracle account is only trusted together with its feed id
+        if price_oracle == Pubkey::default() {
+            config.price_feed_id = [0; 32];
+        } else {
+            require!(
+                params.price_feed_id.is_some(),
+                MvoteError::InvalidConfigValue
+            );
+        }
+        msg!("price_oracle: {} -> {}", config.price_oracle, price_oracle);
+        config.price_oracle = price_oracle;
+    }
+
+    if let Some(price_feed_id) = params.price_feed_id {
+        require!(
+            price_feed_id != [0; 32] && config.price_oracle != Pubkey::default(),
+            MvoteError::InvalidConfigValue
+        );
+        msg!("price_feed_id: {:?}", price_feed_id);
+        config.price_feed_id = price_feed_id;
+    }
+
+    if let Some(max_price_age) = params.max_price_age {
+        require!(
+            (1..=MAX_PRICE_AGE).contains(&max_price_age),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "max_price_age: {} -> {}",
+            config.max_price_age,
+            max_price_age
+        );
+        config.max_price_age = max_price_age;
+    }
+
+    if let Some(max_price_conf_bps) = params.max_price_conf_bps {
+        require!(
+            (1..=MAX_PRICE_CONF_BPS).contains(&max_price_conf_bps),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "max_price_conf_bps: {} -> {}",
+            config.max_price_conf_bps,
+            max_price_conf_bps
+        );
+        config.max_price_conf_bps = max_price_conf_bps;
+    }
+
+    if let Some(redeem_spread_bps) = params.redeem_spread_bps {
+        require!(
+            redeem_spread_bps <= MAX_REDEEM_SPREAD_BPS,
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "redeem_spread_bps: {} -> {}",
+            config.redeem_spread_bps,
+            redeem_spread_bps
+        );
+        config.redeem_spread_bps = redeem_spread_bps;
+    }
+
+    if let Some(daily_redeem_limit) = params.daily_redeem_limit {
+        require!(
+            daily_redeem_limit <= MAX_DAILY_PURCHASE_LIMIT,
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "daily_redeem_limit: {} -> {}",
+            config.daily_redeem_limit,
+            daily_redeem_limit
+        );
+        config.daily_redeem_limit = daily_redeem_limit;
+    }
+
+    if let Some(max_start_lead_time) = params.max_start_lead_time {
+        require!(
+            max_start_lead_time <= MAX_START_LEAD_TIME,
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "max_start_lead_time: {} -> {}",
+            config.max_start_lead_time,
+            max_start_lead_time
+        );
+        config.max_start_lead_time = max_start_lead_time;
+    }
+
+    if let Some(poll_retention_period) = params.poll_retention_period {
+        require!(
+            (MIN_POLL_RETENTION_PERIOD..=MAX_POLL_RETENTION_PERIOD)
+                .contains(&poll_retention_period),
+            MvoteError::InvalidConfigValue
+        );
+        msg!(
+            "poll_retention_period: {} -> {}",
+            config.poll_retention_period,
+            poll_retention_period
+        );
+        config.poll_retention_period = poll_retention_period;
+    }
+
+    msg!("Config updated by admin {}", ctx.accounts.admin.key());
+
+    emit!(ConfigUpdated {
+        admin: ctx.accounts.admin.key(),
+        params,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
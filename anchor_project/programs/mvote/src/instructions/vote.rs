@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
 
 use crate::constants::*;
 use crate::errors::MvoteError;
-use crate::state::{Config, Poll, UserStats, VoteMode, VoteRecord};
+use crate::events::VoteCast;
+use crate::math::isqrt;
+use crate::state::{Config, Poll, UserStats, VoteMode, VoteRecord, VotingStyle};
 
 #[derive(Accounts)]
 #[instruction(poll_id: u64)]
@@ -32,7 +34,7 @@ pub struct Vote<'info> {
     pub poll: Account<'info, Poll>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = voter,
         space = VoteRecord::SIZE,
         seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
@@ -55,6 +57,17 @@ pub struct Vote<'info> {
     )]
     pub voter_token_account: Account<'info, TokenAccount>,
 
+    /// Per-poll escrow, only used by EscrowVote polls
+    #[account(
+        init_if_needed,
+        payer = voter,
+        seeds = [ESCROW_SEED, poll_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = poll
+    )]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
@@ -72,14 +85,25 @@ pub fn handler(
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
-    // Check poll is active
-    require!(poll.is_active, MvoteError::PollNotActive);
+    require!(!config.paused, MvoteError::ProgramPaused);
 
-    // Check poll hasn't expired
-    require!(current_time <= poll.end_time, MvoteError::PollExpired);
+    // Check poll is active (started and not expired, closed or cancelled)
+    poll.require_voting_open(current_time)?;
 
-    // Check poll has started
-    require!(current_time >= poll.start_time, MvoteError::PollNotStarted);
+    // Ranked-choice polls take ballots through vote_ranked
+    require!(
+        poll.voting_style != VotingStyle::RankedChoice,
+        MvoteError::RankedBallotRequired
+    );
+
+    // Approval polls take a bitmask through vote_approval
+    require!(
+        poll.voting_style != VotingStyle::Approval,
+        MvoteError::ApprovalBallotRequired
+    );
+
+    // Secret-ballot polls take commitments through commit_vote
+    require!(!poll.is_secret, MvoteError::CommitmentRequired);
 
     // Check valid option
     require!(
@@ -87,10 +111,17 @@ pub fn handler(
         MvoteError::InvalidOption
     );
 
-    // Calculate tokens to burn based on vote mode
-    let tokens_to_burn = match poll.vote_mode {
+    // FairVote allows one vote per wallet, other modes add to the existing record
+    let is_new_vote = vote_record.voter == Pubkey::default();
+    require!(
+        is_new_vote || poll.vote_mode != VoteMode::FairVote,
+        MvoteError::AlreadyVoted
+    );
+
+    // Calculate tokens to spend based on vote mode
+    let tokens_to_spend = match poll.vote_mode {
         VoteMode::FairVote => config.vote_cost,
-        VoteMode::HoldingVote => {
+        VoteMode::HoldingVote | VoteMode::EscrowVote | VoteMode::Quadratic => {
             require!(token_amount >= config.vote_cost, MvoteError::MinimumVoteRequired);
             token_amount
         }
@@ -98,27 +129,58 @@ pub fn handler(
 
     // Check token balance
     require!(
-        ctx.accounts.voter_token_account.amount >= tokens_to_burn,
+        ctx.accounts.voter_token_account.amount >= tokens_to_spend,
         MvoteError::InsufficientTokens
     );
 
-    // Burn tokens
-    token::burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.token_mint.to_account_info(),
-                from: ctx.accounts.voter_token_account.to_account_info(),
-                authority: ctx.accounts.voter.to_account_info(),
-            },
-        ),
-        tokens_to_burn,
-    )?;
+    if poll.vote_mode == VoteMode::EscrowVote {
+        // Move tokens into the poll escrow
+        let escrow_token_account = ctx
+            .accounts
+            .escrow_token_account
+            .as_ref()
+            .ok_or(MvoteError::EscrowAccountRequired)?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.voter_token_account.to_account_info(),
+                    to: escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            tokens_to_spend,
+        )?;
+    } else {
+        // Burn tokens
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    from: ctx.accounts.voter_token_account.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            tokens_to_spend,
+        )?;
+    }
 
     // Update vote counts
     let vote_weight = match poll.vote_mode {
         VoteMode::FairVote => 1,
-        VoteMode::HoldingVote => tokens_to_burn,
+        VoteMode::HoldingVote | VoteMode::EscrowVote => tokens_to_spend,
+        VoteMode::Quadratic => {
+            // Weight grows with the square root of all tokens this wallet put on the option
+            let previous_tokens = vote_record.option_tokens[option_index as usize];
+            let total_tokens = previous_tokens
+                .checked_add(tokens_to_spend)
+                .ok_or(MvoteError::Overflow)?;
+            isqrt(total_tokens)
+                .checked_sub(isqrt(previous_tokens))
+                .ok_or(MvoteError::Overflow)?
+        }
     };
 
     poll.vote_counts[option_index as usize] = poll.vote_counts[option_index as usize]
@@ -132,16 +194,43 @@ pub fn handler(
 
     poll.total_tokens_spent = poll
         .total_tokens_spent
-        .checked_add(tokens_to_burn)
+        .checked_add(tokens_to_spend)
         .ok_or(MvoteError::Overflow)?;
 
-    // Initialize vote record
-    vote_record.voter = ctx.accounts.voter.key();
-    vote_record.poll_id = poll_id;
+    // Initialize vote record on first vote
+    if is_new_vote {
+        poll.outstanding_records = poll
+            .outstanding_records
+            .checked_add(1)
+            .ok_or(MvoteError::Overflow)?;
+
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.poll_id = poll_id;
+        vote_record.tokens_spent = 0;
+        vote_record.voted_at = current_time;
+        vote_record.vote_count = 0;
+        vote_record.option_tokens = [0; 4];
+        vote_record.tokens_reclaimed = false;
+        vote_record.option_mask = 0;
+        vote_record.commitment = [0; 32];
+        vote_record.is_revealed = false;
+        vote_record.bump = ctx.bumps.vote_record;
+    }
+
+    // Accumulate vote record
     vote_record.option_index = option_index;
-    vote_record.tokens_spent = tokens_to_burn;
-    vote_record.voted_at = current_time;
-    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.tokens_spent = vote_record
+        .tokens_spent
+        .checked_add(tokens_to_spend)
+        .ok_or(MvoteError::Overflow)?;
+    vote_record.vote_count = vote_record
+        .vote_count
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+    vote_record.option_tokens[option_index as usize] = vote_record.option_tokens
+        [option_index as usize]
+        .checked_add(tokens_to_spend)
+        .ok_or(MvoteError::Overflow)?;
 
     // Update user stats
     user_stats.votes_cast = user_stats
@@ -153,8 +242,20 @@ pub fn handler(
         "Vote cast on poll {} for option {} with {} tokens",
         poll_id,
         option_index,
-        tokens_to_burn
+        tokens_to_spend
     );
 
+    emit!(VoteCast {
+        poll_id,
+        poll: poll.key(),
+        voter: ctx.accounts.voter.key(),
+        vote_record: vote_record.key(),
+        option_index,
+        option_mask: 1 << option_index,
+        tokens_spent: tokens_to_spend,
+        vote_weight,
+        timestamp: current_time,
+    });
+
     Ok(())
 }
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteCast;
+use crate::state::{Config, Poll, UserStats, VoteRecord, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64)]
+pub struct VoteApproval<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, voter.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::SIZE,
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<VoteApproval>, poll_id: u64, option_mask: u8) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let poll = &mut ctx.accounts.poll;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(!config.paused, MvoteError::ProgramPaused);
+
+    // Check poll is active
+    poll.require_voting_open(current_time)?;
+
+    require!(
+        poll.voting_style == VotingStyle::Approval,
+        MvoteError::NotApprovalPoll
+    );
+
+    // Validate mask: at least one bit set, none past the last option
+    let option_count = poll.options.len();
+    require!(
+        option_mask != 0 && (option_mask as u32) >> option_count == 0,
+        MvoteError::InvalidOptionMask
+    );
+
+    // Check token balance
+    require!(
+        ctx.accounts.voter_token_account.amount >= config.vote_cost,
+        MvoteError::InsufficientTokens
+    );
+
+    // Burn tokens (one vote cost regardless of how many options are approved)
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        config.vote_cost,
+    )?;
+
+    // Count one vote for every approved option
+    for option in 0..option_count {
+        if option_mask & (1 << option) != 0 {
+            poll.vote_counts[option] = poll.vote_counts[option]
+                .checked_add(1)
+                .ok_or(MvoteError::Overflow)?;
+        }
+    }
+
+    poll.total_votes = poll
+        .total_votes
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.total_tokens_spent = poll
+        .total_tokens_spent
+        .checked_add(config.vote_cost)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.outstanding_records = poll
+        .outstanding_records
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    // Initialize vote record
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.poll_id = poll_id;
+    vote_record.option_index = 0;
+    vote_record.tokens_spent = config.vote_cost;
+    vote_record.voted_at = current_time;
+    vote_record.vote_count = 1;
+    vote_record.option_tokens = [0; 4];
+    vote_record.tokens_reclaimed = false;
+    vote_record.rankings = Vec::new();
+    vote_record.option_mask = option_mask;
+    vote_record.commitment = [0; 32];
+    vote_record.is_revealed = false;
+    vote_record.bump = ctx.bumps.vote_record;
+
+    // Update user stats
+    user_stats.votes_cast = user_stats
+        .votes_cast
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Approval vote cast on poll {} with option mask {:#06b}",
+        poll_id,
+        option_mask
+    );
+
+    emit!(VoteCast {
+        poll_id,
+        poll: poll.key(),
+        voter: ctx.accounts.voter.key(),
+        vote_record: vote_record.key(),
+        option_index: option_mask.trailing_zeros() as u8,
+        option_mask,
+        tokens_spent: config.vote_cost,
+        vote_weight: 1,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
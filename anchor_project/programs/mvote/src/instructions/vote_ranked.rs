@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VoteCast;
+use crate::state::{BallotCount, Config, Poll, RankedBallots, UserStats, VoteRecord, VotingStyle};
+
+#[derive(Accounts)]
+#[instruction(poll_id: u64, rankings: Vec<u8>)]
+pub struct VoteRanked<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [USER_SEED, voter.key().as_ref()],
+        bump = user_stats.bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(
+        mut,
+        seeds = [POLL_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = poll.bump
+    )]
+    pub poll: Account<'info, Poll>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::ranked_size(rankings.len()),
+        seeds = [VOTE_SEED, poll_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        seeds = [BALLOTS_SEED, poll_id.to_le_bytes().as_ref()],
+        bump = ranked_ballots.bump
+    )]
+    pub ranked_ballots: Account<'info, RankedBallots>,
+
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump,
+        constraint = token_mint.key() == config.token_mint
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = voter
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<VoteRanked>, poll_id: u64, rankings: Vec<u8>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let poll = &mut ctx.accounts.poll;
+    let user_stats = &mut ctx.accounts.user_stats;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let ranked_ballots = &mut ctx.accounts.ranked_ballots;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(!config.paused, MvoteError::ProgramPaused);
+
+    // Check poll is active
+    poll.require_voting_open(current_time)?;
+
+    require!(
+        poll.voting_style == VotingStyle::RankedChoice,
+        MvoteError::NotRankedPoll
+    );
+
+    // Validate rankings: 1..=options entries, each a distinct valid option
+    require!(
+        !rankings.is_empty() && rankings.len() <= poll.options.len(),
+        MvoteError::InvalidRankings
+    );
+    for (i, option) in rankings.iter().enumerate() {
+        require!(
+            (*option as usize) < poll.options.len() && !rankings[..i].contains(option),
+            MvoteError::InvalidRankings
+        );
+    }
+
+    // Check token balance
+    require!(
+        ctx.accounts.voter_token_account.amount >= config.vote_cost,
+        MvoteError::InsufficientTokens
+    );
+
+    // Burn tokens
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        config.vote_cost,
+    )?;
+
+    // Aggregate the ballot with identical ones for the tally
+    match ranked_ballots
+        .ballots
+        .iter_mut()
+        .find(|ballot| ballot.rankings == rankings)
+    {
+        Some(ballot) => {
+            ballot.count = ballot.count.checked_add(1).ok_or(MvoteError::Overflow)?;
+        }
+        None => {
+            require!(
+                ranked_ballots.ballots.len() < MAX_RANKED_BALLOTS,
+                MvoteError::Overflow
+            );
+            ranked_ballots.ballots.push(BallotCount {
+                rankings: rankings.clone(),
+                count: 1,
+            });
+        }
+    }
+
+    // Live counts show first preferences
+    let first_choice = rankings[0] as usize;
+    poll.vote_counts[first_choice] = poll.vote_counts[first_choice]
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.total_votes = poll
+        .total_votes
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.total_tokens_spent = poll
+        .total_tokens_spent
+        .checked_add(config.vote_cost)
+        .ok_or(MvoteError::Overflow)?;
+
+    poll.outstanding_records = poll
+        .outstanding_records
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    // Initialize vote record
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.poll_id = poll_id;
+    vote_record.option_index = rankings[0];
+    vote_record.tokens_spent = config.vote_cost;
+    vote_record.voted_at = current_time;
+    vote_record.vote_count = 1;
+    vote_record.option_tokens = [0; 4];
+    vote_record.option_tokens[first_choice] = config.vote_cost;
+    vote_record.tokens_reclaimed = false;
+    vote_record.rankings = rankings;
+    vote_record.option_mask = 0;
+    vote_record.commitment = [0; 32];
+    vote_record.is_revealed = false;
+    vote_record.bump = ctx.bumps.vote_record;
+
+    // Update user stats
+    user_stats.votes_cast = user_stats
+        .votes_cast
+        .checked_add(1)
+        .ok_or(MvoteError::Overflow)?;
+
+    msg!(
+        "Ranked ballot cast on poll {}: {:?}",
+        poll_id,
+        vote_record.rankings
+    );
+
+    emit!(VoteCast {
+        poll_id,
+        poll: poll.key(),
+        voter: ctx.accounts.voter.key(),
+        vote_record: vote_record.key(),
+        option_index: vote_record.option_index,
+        option_mask: 1 << first_choice,
+        tokens_spent: config.vote_cost,
+        vote_weight: 1,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
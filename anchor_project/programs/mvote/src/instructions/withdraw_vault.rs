@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::MvoteError;
+use crate::events::VaultWithdrawn;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct WithdrawVault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ MvoteError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: SOL vault PDA
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ MvoteError::InvalidTreasury
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WithdrawVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, MvoteError::InvalidAmount);
+
+    // Vault must stay rent-exempt after the withdrawal
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let available = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(rent_exempt_minimum);
+    require!(amount <= available, MvoteError::InsufficientVaultBalance);
+
+    // Transfer SOL from vault to treasury
+    let vault_seeds = &[VAULT_SEED, &[ctx.bumps.sol_vault]];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "Withdrew {} lamports from vault to treasury {}",
+        amount,
+        ctx.accounts.treasury.key()
+    );
+
+    emit!(VaultWithdrawn {
+        admin: ctx.accounts.admin.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,12 +1,19 @@
+#![allow(ambiguous_glob_reexports)]
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod math;
+pub mod migration;
+pub mod oracle;
 pub mod state;
 
 use instructions::*;
-use state::{VoteMode, VotingStyle};
+use state::{PollResult, VoteMode, VotingStyle};
 
 declare_id!("5BWRzdnfyupL8h4MpdHVgZT5bibUeLq85qrDUsu7QVmZ");
 
@@ -21,10 +28,20 @@ pub mod mvote {
 
     /// Purchase mVote tokens with SOL
     pub fn purchase_tokens(ctx: Context<PurchaseTokens>, amount: u64) -> Result<()> {
-        instructions::purchase_tokens::handler(ctx, amount)
+        instructions::purchase_tokens::handler(ctx, amount, None)
     }
 
-    /// Create a new poll
+    /// Purchase mVote tokens with SOL, failing if the cost exceeds max_lamports
+    pub fn purchase_tokens_with_max_cost(
+        ctx: Context<PurchaseTokens>,
+        amount: u64,
+        max_lamports: u64,
+    ) -> Result<()> {
+        instructions::purchase_tokens::handler(ctx, amount, Some(max_lamports))
+    }
+
+    /// Create a new poll, optionally scheduled to start in the future
+    #[allow(clippy::too_many_arguments)]
     pub fn create_poll(
         ctx: Context<CreatePoll>,
         question: String,
@@ -32,8 +49,45 @@ pub mod mvote {
         vote_mode: VoteMode,
         voting_style: VotingStyle,
         duration_minutes: u16,
+        start_time: Option<i64>,
+        pass_rules: Option<PassRules>,
+    ) -> Result<()> {
+        instructions::create_poll::handler(
+            ctx,
+            question,
+            options,
+            vote_mode,
+            voting_style,
+            duration_minutes,
+            start_time,
+            pass_rules,
+            None,
+        )
+    }
+
+    /// Create a secret-ballot poll whose votes are revealed after it ends
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_secret_poll(
+        ctx: Context<CreatePoll>,
+        question: String,
+        options: Vec<String>,
+        voting_style: VotingStyle,
+        duration_minutes: u16,
+        reveal_duration_minutes: u16,
+        start_time: Option<i64>,
+        pass_rules: Option<PassRules>,
     ) -> Result<()> {
-        instructions::create_poll::handler(ctx, question, options, vote_mode, voting_style, duration_minutes)
+        instructions::create_poll::handler(
+            ctx,
+            question,
+            options,
+            VoteMode::FairVote,
+            voting_style,
+            duration_minutes,
+            start_time,
+            pass_rules,
+            Some(reveal_duration_minutes),
+        )
     }
 
     /// Cast a vote on a poll
@@ -50,4 +104,143 @@ pub mod mvote {
     pub fn close_poll(ctx: Context<ClosePoll>, poll_id: u64) -> Result<()> {
         instructions::close_poll::handler(ctx, poll_id)
     }
+
+    /// Update pricing and limits (admin only)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    /// Propose a new admin (admin only, must be accepted by the new admin)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
+
+    /// Accept a pending admin transfer
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Withdraw SOL from the vault to the treasury (admin only)
+    pub fn withdraw_vault(ctx: Context<WithdrawVault>, amount: u64) -> Result<()> {
+        instructions::withdraw_vault::handler(ctx, amount)
+    }
+
+    /// Pause or unpause purchases, poll creation and voting (admin only)
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        instructions::set_pause::handler(ctx, paused)
+    }
+
+    /// Redeem mVote tokens for SOL from the vault
+    pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+        instructions::redeem_tokens::handler(ctx, amount)
+    }
+
+    /// Move an existing vote to a different option
+    pub fn change_vote(ctx: Context<ChangeVote>, poll_id: u64, new_option_index: u8) -> Result<()> {
+        instructions::change_vote::handler(ctx, poll_id, new_option_index)
+    }
+
+    /// Retract an existing vote and close its vote record
+    pub fn retract_vote(ctx: Context<RetractVote>, poll_id: u64) -> Result<()> {
+        instructions::retract_vote::handler(ctx, poll_id)
+    }
+
+    /// Enable or disable vote changes on a poll (creator only)
+    pub fn set_vote_changes(
+        ctx: Context<SetVoteChanges>,
+        poll_id: u64,
+        allowed: bool,
+    ) -> Result<()> {
+        instructions::set_vote_changes::handler(ctx, poll_id, allowed)
+    }
+
+    /// Return escrowed vote tokens after the poll ends or is closed
+    pub fn reclaim_vote_tokens(ctx: Context<ReclaimVoteTokens>, poll_id: u64) -> Result<()> {
+        instructions::reclaim_vote_tokens::handler(ctx, poll_id)
+    }
+
+    /// Cast a ranked ballot on a ranked-choice poll
+    pub fn vote_ranked(ctx: Context<VoteRanked>, poll_id: u64, rankings: Vec<u8>) -> Result<()> {
+        instructions::vote_ranked::handler(ctx, poll_id, rankings)
+    }
+
+    /// Run the instant-runoff tally after a ranked-choice poll ends
+    pub fn tally_ranked(ctx: Context<TallyRanked>, poll_id: u64) -> Result<()> {
+        instructions::tally_ranked::handler(ctx, poll_id)
+    }
+
+    /// Approve any subset of options on an approval poll
+    pub fn vote_approval(ctx: Context<VoteApproval>, poll_id: u64, option_mask: u8) -> Result<()> {
+        instructions::vote_approval::handler(ctx, poll_id, option_mask)
+    }
+
+    /// Commit a hidden vote on a secret-ballot poll
+    pub fn commit_vote(ctx: Context<CommitVote>, poll_id: u64, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_vote::handler(ctx, poll_id, commitment)
+    }
+
+    /// Reveal a committed vote during the reveal window
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        poll_id: u64,
+        option_index: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_vote::handler(ctx, poll_id, option_index, salt)
+    }
+
+    /// Record the outcome of an ended poll (permissionless)
+    pub fn finalize_poll(ctx: Context<FinalizePoll>, poll_id: u64) -> Result<()> {
+        instructions::finalize_poll::handler(ctx, poll_id)
+    }
+
+    /// Return the poll outcome in the fixed `PollResult` layout
+    pub fn get_poll_result(ctx: Context<GetPollResult>, poll_id: u64) -> Result<PollResult> {
+        instructions::get_poll_result::handler(ctx, poll_id)
+    }
+
+    /// Rewrite a poll created before the status enum into the current layout
+    pub fn migrate_poll(ctx: Context<MigratePoll>, poll_id: u64) -> Result<()> {
+        instructions::migrate_poll::handler(ctx, poll_id)
+    }
+
+    /// Cancel a scheduled or active poll and make its votes refundable (creator only)
+    pub fn cancel_poll(ctx: Context<CancelPoll>, poll_id: u64) -> Result<()> {
+        instructions::cancel_poll::handler(ctx, poll_id)
+    }
+
+    /// Refund the tokens spent on a vote in a cancelled poll
+    pub fn claim_refund(ctx: Context<ClaimRefund>, poll_id: u64) -> Result<()> {
+        instructions::claim_refund::handler(ctx, poll_id)
+    }
+
+    /// Close a vote record after the poll is finalized, returning its rent
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>, poll_id: u64) -> Result<()> {
+        instructions::close_vote_record::handler(ctx, poll_id)
+    }
+
+    /// Archive a finished poll into a summary and return its rent (creator only)
+    pub fn close_poll_account(ctx: Context<ClosePollAccount>, poll_id: u64) -> Result<()> {
+        instructions::close_poll_account::handler(ctx, poll_id)
+    }
+
+    /// Rewrite the config created by the original deployment into the current
+    /// layout, filling new settings with defaults (admin only)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    /// Rewrite user stats created by the original deployment into the current layout
+    pub fn migrate_user_stats(ctx: Context<MigrateUserStats>, wallet: Pubkey) -> Result<()> {
+        instructions::migrate_user_stats::handler(ctx, wallet)
+    }
+
+    /// Rewrite a vote record created by the original deployment into the current layout
+    pub fn migrate_vote_record(
+        ctx: Context<MigrateVoteRecord>,
+        poll_id: u64,
+        voter: Pubkey,
+    ) -> Result<()> {
+        instructions::migrate_vote_record::handler(ctx, poll_id, voter)
+    }
 }
@@ -0,0 +1,17 @@
+/// Integer square root (floor), computed with Newton's method
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // u128 keeps (x + n / x) from overflowing near u64::MAX
+    let n = n as u128;
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x as u64
+}
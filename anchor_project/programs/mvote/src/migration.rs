@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::MvoteError;
+
+// Shared steps for rewriting accounts created by the originally deployed
+// program into their current, larger layout.
+
+/// Deserialize a legacy account of type `L` stored under `discriminator`.
+/// Accounts already at `current_size` or larger are rejected as migrated.
+pub fn read_legacy<L: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    current_size: usize,
+) -> Result<L> {
+    let data = account.try_borrow_data()?;
+    require!(data.len() < current_size, MvoteError::AlreadyMigrated);
+    require!(
+        data.len() >= 8 && data[..8] == *discriminator,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(L::deserialize(&mut &data[8..])?)
+}
+
+/// Top up rent from `payer`, grow `account` to `size` and write `value` into it
+pub fn rewrite<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    size: usize,
+    value: &T,
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(size)
+        .saturating_sub(account.lamports());
+    if required_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+    account.resize(size)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
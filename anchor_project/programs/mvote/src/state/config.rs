@@ -21,6 +21,28 @@ pub struct Config {
     pub vote_cost: u64,
     /// Total polls created (used for poll ID)
     pub total_polls_created: u64,
+    /// Proposed new admin awaiting acceptance (default pubkey if none)
+    pub pending_admin: Pubkey,
+    /// Destination for SOL withdrawn from the vault
+    pub treasury: Pubkey,
+    /// Emergency pause for purchases, poll creation and voting
+    pub paused: bool,
+    /// SOL/USD price feed account (default pubkey = use fixed sol_usd_rate)
+    pub price_oracle: Pubkey,
+    /// Pyth feed id the price oracle account must carry (zero if no oracle)
+    pub price_feed_id: [u8; 32],
+    /// Maximum oracle price age in seconds
+    pub max_price_age: u64,
+    /// Maximum oracle confidence interval in basis points of the price
+    pub max_price_conf_bps: u16,
+    /// Spread deducted from redemption payouts in basis points
+    pub redeem_spread_bps: u16,
+    /// Daily redemption limit (with decimals)
+    pub daily_redeem_limit: u64,
+    /// Maximum seconds ahead of creation a poll can be scheduled to start
+    pub max_start_lead_time: u64,
+    /// Seconds after finalization or cancellation before a poll account can be closed
+    pub poll_retention_period: u64,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -36,5 +58,16 @@ impl Config {
         8 +  // poll_creation_cost
         8 +  // vote_cost
         8 +  // total_polls_created
+        32 + // pending_admin
+        32 + // treasury
+        1 +  // paused
+        32 + // price_oracle
+        32 + // price_feed_id
+        8 +  // max_price_age
+        2 +  // max_price_conf_bps
+        2 +  // redeem_spread_bps
+        8 +  // daily_redeem_limit
+        8 +  // max_start_lead_time
+        8 +  // poll_retention_period
         1;   // bump
 }
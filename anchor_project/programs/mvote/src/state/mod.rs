@@ -1,9 +1,13 @@
 pub mod config;
 pub mod poll;
+pub mod poll_summary;
+pub mod ranked_ballots;
 pub mod user_stats;
 pub mod vote_record;
 
 pub use config::*;
 pub use poll::*;
+pub use poll_summary::*;
+pub use ranked_ballots::*;
 pub use user_stats::*;
 pub use vote_record::*;
@@ -1,11 +1,17 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::MvoteError;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum VoteMode {
     /// Each user can vote once, costs 1 mVote
     FairVote,
     /// Users can vote multiple times with any amount of mVote
     HoldingVote,
+    /// Like HoldingVote, but tokens are escrowed and reclaimable after the poll ends
+    EscrowVote,
+    /// Like HoldingVote, but vote weight is the square root of tokens burned
+    Quadratic,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -14,6 +20,60 @@ pub enum VotingStyle {
     Binary,
     /// Multiple choice options (A, B, C, D)
     MultipleChoice,
+    /// Ranked ballots tallied by instant-runoff (FairVote only)
+    RankedChoice,
+    /// Voters approve any subset of options in one vote (FairVote only)
+    Approval,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PollStatus {
+    /// Created with a future start_time, not accepting votes yet
+    Scheduled,
+    /// Accepting votes
+    Active,
+    /// Closed by the creator before end_time
+    ClosedEarly,
+    /// Reached end_time
+    Expired,
+    /// Outcome computed and stored by finalize_poll
+    Finalized,
+    /// Cancelled by the creator
+    Cancelled,
+}
+
+impl PollStatus {
+    /// Whether voting has ended for good
+    pub fn is_voting_over(self) -> bool {
+        matches!(
+            self,
+            PollStatus::ClosedEarly
+                | PollStatus::Expired
+                | PollStatus::Finalized
+                | PollStatus::Cancelled
+        )
+    }
+}
+
+/// Minimum participation for a poll to pass
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Quorum {
+    /// Minimum total_votes
+    Votes(u64),
+    /// Minimum total_tokens_spent
+    Tokens(u64),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum PollOutcome {
+    /// Not finalized yet, or the poll has no pass threshold
+    Undecided,
+    /// Quorum met and the winner reached the pass threshold
+    Passed,
+    /// Quorum met but no winner reached the pass threshold
+    Failed,
+    /// Participation stayed below the quorum
+    QuorumNotMet,
 }
 
 #[account]
@@ -44,8 +104,40 @@ pub struct Poll {
     pub total_votes: u64,
     /// Total tokens spent on this poll
     pub total_tokens_spent: u64,
-    /// Whether the poll is active
-    pub is_active: bool,
+    /// Lifecycle status (time-based transitions are applied by `sync_status`)
+    pub status: PollStatus,
+    /// Whether voters can change or retract their votes
+    pub allow_vote_changes: bool,
+    /// Whether the ranked-choice tally has run
+    pub is_tallied: bool,
+    /// Winning option (None on a tie or if no votes were cast), set by
+    /// tally_ranked for ranked-choice polls and by finalize_poll otherwise
+    pub winning_option: Option<u8>,
+    /// Active-ballot counts per option for each instant-runoff round
+    #[max_len(3)]
+    pub tally_rounds: Vec<[u64; 4]>,
+    /// Whether votes are committed as hashes and revealed after end_time
+    pub is_secret: bool,
+    /// End of the reveal window (0 for non-secret polls)
+    pub reveal_end_time: i64,
+    /// Number of secret-ballot commitments
+    pub committed_votes: u64,
+    /// Number of commitments revealed and added to the tally
+    pub revealed_votes: u64,
+    /// Whether the top vote count was shared, leaving no winner
+    pub is_tie: bool,
+    /// Timestamp when the poll was finalized (0 until then)
+    pub finalized_at: i64,
+    /// Timestamp when the poll was cancelled (0 unless cancelled)
+    pub cancelled_at: i64,
+    /// Participation required to pass (None = no quorum)
+    pub quorum: Option<Quorum>,
+    /// Winner's share of the vote required to pass, in basis points (0 = no pass rules)
+    pub pass_threshold_bps: u16,
+    /// Pass/fail result, set by finalize_poll
+    pub outcome: PollOutcome,
+    /// Vote records whose tokens have not been refunded, reclaimed or retracted
+    pub outstanding_records: u64,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -64,6 +156,112 @@ impl Poll {
         8 +                         // end_time
         8 +                         // total_votes
         8 +                         // total_tokens_spent
-        1 +                         // is_active
+        1 +                         // status
+        1 +                         // allow_vote_changes
+        1 +                         // is_tallied
+        1 + 1 +                     // winning_option (option tag + u8)
+        4 + (3 * 4 * 8) +           // tally_rounds (vec prefix + 3 rounds * 4 * u64)
+        1 +                         // is_secret
+        8 +                         // reveal_end_time
+        8 +                         // committed_votes
+        8 +                         // revealed_votes
+        1 +                         // is_tie
+        8 +                         // finalized_at
+        8 +                         // cancelled_at
+        1 + 1 + 8 +                 // quorum (option tag + variant + u64)
+        2 +                         // pass_threshold_bps
+        1 +                         // outcome
+        8 +                         // outstanding_records
         1;                          // bump
+
+    /// Status with time-based transitions applied: Scheduled becomes Active
+    /// at start_time, and Scheduled or Active becomes Expired after end_time
+    pub fn current_status(&self, current_time: i64) -> PollStatus {
+        match self.status {
+            PollStatus::Scheduled | PollStatus::Active if current_time > self.end_time => {
+                PollStatus::Expired
+            }
+            PollStatus::Scheduled if current_time >= self.start_time => PollStatus::Active,
+            status => status,
+        }
+    }
+
+    /// Store the time-based status transitions and return the new status
+    pub fn sync_status(&mut self, current_time: i64) -> PollStatus {
+        self.status = self.current_status(current_time);
+        self.status
+    }
+
+    /// Sync the status and require the poll to be accepting votes
+    pub fn require_voting_open(&mut self, current_time: i64) -> Result<()> {
+        match self.sync_status(current_time) {
+            PollStatus::Active => Ok(()),
+            PollStatus::Scheduled => err!(MvoteError::PollNotStarted),
+            PollStatus::Expired => err!(MvoteError::PollExpired),
+            _ => err!(MvoteError::PollNotActive),
+        }
+    }
+
+    /// Secret-ballot commitments that were never revealed
+    pub fn unrevealed_votes(&self) -> u64 {
+        self.committed_votes.saturating_sub(self.revealed_votes)
+    }
+}
+
+/// Fixed-size poll outcome returned by `get_poll_result` for CPI callers.
+///
+/// Borsh layout (little-endian, no discriminator), stable across upgrades;
+/// new fields are only ever appended:
+///
+/// | Offset | Size | Field                                      |
+/// |--------|------|--------------------------------------------|
+/// | 0      | 8    | poll_id (u64)                              |
+/// | 8      | 1    | status (0 = Scheduled, 1 = Active,         |
+/// |        |      | 2 = ClosedEarly, 3 = Expired,              |
+/// |        |      | 4 = Finalized, 5 = Cancelled)              |
+/// | 9      | 1    | has_winner (bool)                          |
+/// | 10     | 1    | winning_option (u8, 0 when has_winner = 0) |
+/// | 11     | 1    | is_tie (bool)                              |
+/// | 12     | 8    | total_votes (u64)                          |
+/// | 20     | 8    | total_tokens_spent (u64)                   |
+/// | 28     | 32   | vote_counts ([u64; 4], unused options = 0) |
+/// | 60     | 8    | finalized_at (i64, 0 until finalized)      |
+/// | 68     | 1    | outcome (0 = Undecided, 1 = Passed,        |
+/// |        |      | 2 = Failed, 3 = QuorumNotMet)              |
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PollResult {
+    pub poll_id: u64,
+    pub status: PollStatus,
+    pub has_winner: bool,
+    pub winning_option: u8,
+    pub is_tie: bool,
+    pub total_votes: u64,
+    pub total_tokens_spent: u64,
+    pub vote_counts: [u64; 4],
+    pub finalized_at: i64,
+    pub outcome: PollOutcome,
+}
+
+impl PollResult {
+    pub const SIZE: usize = 69;
+}
+
+impl From<&Poll> for PollResult {
+    fn from(poll: &Poll) -> Self {
+        let mut vote_counts = [0u64; 4];
+        vote_counts[..poll.vote_counts.len()].copy_from_slice(&poll.vote_counts);
+
+        Self {
+            poll_id: poll.id,
+            status: poll.status,
+            has_winner: poll.winning_option.is_some(),
+            winning_option: poll.winning_option.unwrap_or(0),
+            is_tie: poll.is_tie,
+            total_votes: poll.total_votes,
+            total_tokens_spent: poll.total_tokens_spent,
+            vote_counts,
+            finalized_at: poll.finalized_at,
+            outcome: poll.outcome,
+        }
+    }
 }
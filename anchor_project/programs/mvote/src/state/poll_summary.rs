@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PollOutcome, PollStatus, VoteMode, VotingStyle};
+
+/// Compact record of a poll's results, kept after the poll account is closed
+#[account]
+#[derive(InitSpace)]
+pub struct PollSummary {
+    /// Poll ID this summary archives
+    pub poll_id: u64,
+    /// Poll creator
+    pub creator: Pubkey,
+    /// Voting mode
+    pub vote_mode: VoteMode,
+    /// Voting style
+    pub voting_style: VotingStyle,
+    /// Final status (Finalized or Cancelled)
+    pub status: PollStatus,
+    /// Pass/fail result
+    pub outcome: PollOutcome,
+    /// Winning option (None on a tie, with no votes, or if cancelled)
+    pub winning_option: Option<u8>,
+    /// Whether the top vote count was shared
+    pub is_tie: bool,
+    /// Number of options
+    pub option_count: u8,
+    /// Vote counts for each option (unused options = 0)
+    pub vote_counts: [u64; 4],
+    /// Total number of votes cast
+    pub total_votes: u64,
+    /// Total tokens spent on this poll
+    pub total_tokens_spent: u64,
+    /// Poll end time (unix timestamp)
+    pub end_time: i64,
+    /// Timestamp when the poll was finalized (0 if cancelled)
+    pub finalized_at: i64,
+    /// Timestamp when the poll was cancelled (0 if finalized)
+    pub cancelled_at: i64,
+    /// Timestamp when the poll account was closed
+    pub archived_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PollSummary {
+    pub const SIZE: usize = 8 + // discriminator
+        8 +  // poll_id
+        32 + // creator
+        1 +  // vote_mode
+        1 +  // voting_style
+        1 +  // status
+        1 +  // outcome
+        1 + 1 + // winning_option (option tag + u8)
+        1 +  // is_tie
+        1 +  // option_count
+        (4 * 8) + // vote_counts
+        8 +  // total_votes
+        8 +  // total_tokens_spent
+        8 +  // end_time
+        8 +  // finalized_at
+        8 +  // cancelled_at
+        8 +  // archived_at
+        1;   // bump
+}
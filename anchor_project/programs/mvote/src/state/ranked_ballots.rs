@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct BallotCount {
+    /// Option indices, most preferred first
+    #[max_len(4)]
+    pub rankings: Vec<u8>,
+    /// Number of identical ballots cast
+    pub count: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RankedBallots {
+    /// Poll ID these ballots belong to
+    pub poll_id: u64,
+    /// Identical ballots aggregated into one entry each
+    #[max_len(64)]
+    pub ballots: Vec<BallotCount>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl RankedBallots {
+    pub const SIZE: usize = 8 + // discriminator
+        8 +  // poll_id
+        4 + (64 * (4 + 4 + 8)) + // ballots (vec prefix + 64 * (rankings + count))
+        1;   // bump
+}
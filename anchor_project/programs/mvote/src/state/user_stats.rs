@@ -15,6 +15,12 @@ pub struct UserStats {
     pub polls_created: u64,
     /// Number of votes cast
     pub votes_cast: u64,
+    /// Tokens redeemed today
+    pub tokens_redeemed_today: u64,
+    /// Last redemption day (unix timestamp of day start in SGT)
+    pub last_redeem_day: i64,
+    /// Total tokens ever redeemed
+    pub total_tokens_redeemed: u64,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -27,5 +33,8 @@ impl UserStats {
         8 +  // total_tokens_purchased
         8 +  // polls_created
         8 +  // votes_cast
+        8 +  // tokens_redeemed_today
+        8 +  // last_redeem_day
+        8 +  // total_tokens_redeemed
         1;   // bump
 }
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::math::isqrt;
+use crate::state::VoteMode;
+
 #[account]
 #[derive(InitSpace)]
 pub struct VoteRecord {
@@ -7,12 +10,28 @@ pub struct VoteRecord {
     pub voter: Pubkey,
     /// Poll ID this vote is for
     pub poll_id: u64,
-    /// Index of the chosen option
+    /// Index of the chosen option (most recent one in HoldingVote)
     pub option_index: u8,
     /// Total tokens spent on this vote
     pub tokens_spent: u64,
-    /// Timestamp when vote was cast
+    /// Timestamp when vote was first cast
     pub voted_at: i64,
+    /// Number of votes cast (always 1 in FairVote)
+    pub vote_count: u32,
+    /// Tokens spent per option
+    pub option_tokens: [u64; 4],
+    /// Whether spent tokens have been returned to the voter (escrow reclaim
+    /// or cancellation refund)
+    pub tokens_reclaimed: bool,
+    /// Ranked-choice ballot, most preferred first (empty for other styles)
+    #[max_len(4)]
+    pub rankings: Vec<u8>,
+    /// Approval ballot, bit i set when option i is approved (0 for other styles)
+    pub option_mask: u8,
+    /// Secret-ballot commitment, sha256(option_index || salt || voter)
+    pub commitment: [u8; 32],
+    /// Whether the secret-ballot commitment has been revealed
+    pub is_revealed: bool,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -24,5 +43,30 @@ impl VoteRecord {
         1 +  // option_index
         8 +  // tokens_spent
         8 +  // voted_at
+        4 +  // vote_count
+        (4 * 8) + // option_tokens
+        1 +  // tokens_reclaimed
+        4 +  // rankings (vec prefix, entries sized per ballot)
+        1 +  // option_mask
+        32 + // commitment
+        1 +  // is_revealed
         1;   // bump
+
+    /// Size of a record holding a ranked ballot with `ranking_count` entries
+    pub fn ranked_size(ranking_count: usize) -> usize {
+        Self::SIZE + ranking_count
+    }
+
+    /// Weight this record contributes to `Poll.vote_counts[option]`
+    pub fn weight_for(&self, vote_mode: VoteMode, option: usize) -> u64 {
+        if self.option_mask != 0 {
+            return u64::from(self.option_mask & (1 << option) != 0);
+        }
+
+        match vote_mode {
+            VoteMode::FairVote => u64::from(option == self.option_index as usize),
+            VoteMode::HoldingVote | VoteMode::EscrowVote => self.option_tokens[option],
+            VoteMode::Quadratic => isqrt(self.option_tokens[option]),
+        }
+    }
 }
@@ -0,0 +1,49 @@
+// Writes mock_sol_usd_price.json, a fully verified Pyth PriceUpdateV2
+// account for SOL/USD at $200.00 published now. Run by the pre-test hook in
+// Anchor.toml so the price is fresh when the test validator loads it; see
+// programs/mvote/src/oracle.rs for the layout.
+const fs = require("fs");
+const path = require("path");
+
+const PYTH_RECEIVER = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ";
+const ADDRESS = "GEh5GddbvuSipYvnMBjGt6GVnSVir4BRhasgp71J6DxM";
+const DISCRIMINATOR = [34, 241, 35, 99, 157, 126, 244, 205];
+const SOL_USD_FEED_ID =
+  "2efdfc62e56a3c17d9ac35a4259b14917250ee3937edc13bbc68b262c37725c1";
+
+const price = BigInt(20_000_000_000); // $200.00 at exponent -8
+const conf = BigInt(10_000_000); // $0.10
+const publishTime = BigInt(Math.floor(Date.now() / 1000));
+
+const data = Buffer.alloc(134);
+Buffer.from(DISCRIMINATOR).copy(data, 0);
+// write_authority (8..40) stays zeroed
+data.writeUInt8(1, 40); // verification_level = Full
+Buffer.from(SOL_USD_FEED_ID, "hex").copy(data, 41);
+data.writeBigInt64LE(price, 73);
+data.writeBigUInt64LE(conf, 81);
+data.writeInt32LE(-8, 89);
+data.writeBigInt64LE(publishTime, 93);
+data.writeBigInt64LE(publishTime, 101); // prev_publish_time
+data.writeBigInt64LE(price, 109); // ema_price
+data.writeBigUInt64LE(conf, 117); // ema_conf
+data.writeBigUInt64LE(BigInt(1), 125); // posted_slot
+
+const account = {
+  pubkey: ADDRESS,
+  account: {
+    lamports: 1823040,
+    data: [data.toString("base64"), "base64"],
+    owner: PYTH_RECEIVER,
+    executable: false,
+    rentEpoch: "RENT_EPOCH",
+    space: data.length,
+  },
+};
+
+// u64::MAX does not survive a JS number
+const json = JSON.stringify(account, null, 2).replace(
+  '"RENT_EPOCH"',
+  "18446744073709551615"
+);
+fs.writeFileSync(path.join(__dirname, "mock_sol_usd_price.json"), json + "\n");
@@ -11,9 +11,11 @@ import {
   TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
   createAssociatedTokenAccountInstruction,
+  createTransferInstruction,
   getAccount,
 } from "@solana/spl-token";
 import { assert, expect } from "chai";
+import { createHash, randomBytes } from "crypto";
 
 describe("mvote", () => {
   const provider = anchor.AnchorProvider.env();
@@ -36,6 +38,43 @@ describe("mvote", () => {
   let user1TokenAccount: PublicKey;
   let user2TokenAccount: PublicKey;
 
+  // Empty update_config params (every field left unchanged)
+  const emptyParams = {
+    solUsdRate: null,
+    tokensPerUsd: null,
+    dailyPurchaseLimit: null,
+    pollCreationCost: null,
+    voteCost: null,
+    treasury: null,
+    priceOracle: null,
+    priceFeedId: null,
+    maxPriceAge: null,
+    maxPriceConfBps: null,
+    redeemSpreadBps: null,
+    dailyRedeemLimit: null,
+    maxStartLeadTime: null,
+    pollRetentionPeriod: null,
+  };
+
+  // Mock Pyth price feeds loaded from tests/fixtures ($200.00 SOL/USD). The
+  // fresh one is written with the current time by the pre-test hook.
+  const mockPriceFeed = new PublicKey(
+    "GEh5GddbvuSipYvnMBjGt6GVnSVir4BRhasgp71J6DxM"
+  );
+  const stalePriceFeed = new PublicKey(
+    "DD9aBXn9YQG9mae5PDGCF7jqY4ZNT6Wgi81KbLJBneP7"
+  );
+  const futurePriceFeed = new PublicKey(
+    "68N8PgsDRc4EVQEEP2EtmDWPiAz2jckewKGhywNdixUJ"
+  );
+  // SOL/USD feed id stored in both mock price feeds
+  const solUsdFeedId = Array.from(
+    Buffer.from(
+      "2efdfc62e56a3c17d9ac35a4259b14917250ee3937edc13bbc68b262c37725c1",
+      "hex"
+    )
+  );
+
   // User stats PDAs
   let adminStatsPda: PublicKey;
   let user1StatsPda: PublicKey;
@@ -98,6 +137,48 @@ describe("mvote", () => {
     );
   });
 
+  // Fresh wallet with SOL, a token account and `tokens` mVote
+  const createFundedUser = async (tokens: number) => {
+    const user = Keypair.generate();
+    const airdropTx = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(airdropTx);
+
+    const tokenAccount = getAssociatedTokenAddressSync(mintPda, user.publicKey);
+    const tx = new anchor.web3.Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        user.publicKey,
+        tokenAccount,
+        user.publicKey,
+        mintPda
+      )
+    );
+    await provider.sendAndConfirm(tx, [user]);
+
+    const [statsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), user.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .purchaseTokens(new anchor.BN(tokens))
+      .accounts({
+        buyer: user.publicKey,
+        config: configPda,
+        userStats: statsPda,
+        tokenMint: mintPda,
+        buyerTokenAccount: tokenAccount,
+        solVault: vaultPda,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    return { user, tokenAccount, statsPda };
+  };
+
   describe("Initialize", () => {
     it("should initialize the program", async () => {
       const tx = await program.methods
@@ -145,6 +226,148 @@ describe("mvote", () => {
     });
   });
 
+  describe("Update Config", () => {
+    it("should update config as admin", async () => {
+      await program.methods
+        .updateConfig({ ...emptyParams, solUsdRate: new anchor.BN(20000) })
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      let config = await program.account.config.fetch(configPda);
+      assert.equal(config.solUsdRate.toNumber(), 20000);
+      assert.equal(config.voteCost.toNumber(), 1_000_000);
+
+      // Restore the default rate for the remaining tests
+      await program.methods
+        .updateConfig({ ...emptyParams, solUsdRate: new anchor.BN(15000) })
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      config = await program.account.config.fetch(configPda);
+      assert.equal(config.solUsdRate.toNumber(), 15000);
+    });
+
+    it("should fail to update config by non-admin", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...emptyParams, voteCost: new anchor.BN(2_000_000) })
+          .accounts({
+            admin: user1.publicKey,
+            config: configPda,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+      }
+    });
+
+    it("should fail with out-of-range config value", async () => {
+      try {
+        await program.methods
+          .updateConfig({ ...emptyParams, voteCost: new anchor.BN(0) })
+          .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidConfigValue");
+      }
+    });
+  });
+
+  describe("Admin Transfer", () => {
+    it("should fail to propose admin by non-admin", async () => {
+      try {
+        await program.methods
+          .proposeAdmin(user2.publicKey)
+          .accounts({
+            admin: user1.publicKey,
+            config: configPda,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+      }
+    });
+
+    it("should propose a new admin", async () => {
+      await program.methods
+        .proposeAdmin(user1.publicKey)
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      assert.ok(config.admin.equals(admin.publicKey));
+      assert.ok(config.pendingAdmin.equals(user1.publicKey));
+    });
+
+    it("should fail to accept admin by non-pending signer", async () => {
+      try {
+        await program.methods
+          .acceptAdmin()
+          .accounts({
+            newAdmin: user2.publicKey,
+            config: configPda,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("NotPendingAdmin");
+      }
+    });
+
+    it("should accept admin and transfer back", async () => {
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          newAdmin: user1.publicKey,
+          config: configPda,
+        })
+        .signers([user1])
+        .rpc();
+
+      let config = await program.account.config.fetch(configPda);
+      assert.ok(config.admin.equals(user1.publicKey));
+      assert.ok(config.pendingAdmin.equals(PublicKey.default));
+
+      // Hand admin back to the provider wallet for the remaining tests
+      await program.methods
+        .proposeAdmin(admin.publicKey)
+        .accounts({
+          admin: user1.publicKey,
+          config: configPda,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .acceptAdmin()
+        .accounts({
+          newAdmin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      config = await program.account.config.fetch(configPda);
+      assert.ok(config.admin.equals(admin.publicKey));
+    });
+  });
+
   describe("Purchase Tokens", () => {
     before(async () => {
       // Create associated token accounts for all users
@@ -287,546 +510,3316 @@ describe("mvote", () => {
     });
   });
 
-  describe("Create Poll", () => {
-    before(async () => {
-      // Purchase tokens for user1
-      const amount = new anchor.BN(50_000_000); // 50 mVote
-      await program.methods
-        .purchaseTokens(amount)
-        .accounts({
-          buyer: user1.publicKey,
-          config: configPda,
-          userStats: user1StatsPda,
-          tokenMint: mintPda,
-          buyerTokenAccount: user1TokenAccount,
-          solVault: vaultPda,
-          systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([user1])
-        .rpc();
-    });
-
-    it("should create a binary poll (FairVote)", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
-
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
-      const tx = await program.methods
-        .createPoll(
-          "Should we implement dark mode?",
-          ["Yes", "No"],
-          { fairVote: {} },
-          { binary: {} },
-          60 // 60 minutes
-        )
-        .accounts({
-          creator: user1.publicKey,
-          config: configPda,
-          userStats: user1StatsPda,
-          poll: pollPda,
-          tokenMint: mintPda,
-          creatorTokenAccount: user1TokenAccount,
-          systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
-        })
-        .signers([user1])
-        .rpc();
-
-      console.log("Create poll tx:", tx);
-
-      // Verify poll
-      const poll = await program.account.poll.fetch(pollPda);
-      assert.equal(poll.question, "Should we implement dark mode?");
-      assert.equal(poll.options.length, 2);
-      assert.deepEqual(poll.voteMode, { fairVote: {} });
-      assert.equal(poll.isActive, true);
-    });
-
-    it("should create a multiple choice poll (HoldingVote)", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
-
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+  describe("Withdraw Vault", () => {
+    it("should withdraw a partial amount to the treasury", async () => {
+      const amount = new anchor.BN(1_000_000); // 0.001 SOL
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
 
       await program.methods
-        .createPoll(
-          "What feature should we build next?",
-          ["Mobile App", "API Access", "Dark Mode", "Notifications"],
-          { holdingVote: {} },
-          { multipleChoice: {} },
-          120 // 2 hours
-        )
+        .withdrawVault(amount)
         .accounts({
-          creator: user1.publicKey,
+          admin: admin.publicKey,
           config: configPda,
-          userStats: user1StatsPda,
-          poll: pollPda,
-          tokenMint: mintPda,
-          creatorTokenAccount: user1TokenAccount,
+          solVault: vaultPda,
+          treasury: admin.publicKey,
           systemProgram: SystemProgram.programId,
-          tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([user1])
         .rpc();
 
-      const poll = await program.account.poll.fetch(pollPda);
-      assert.equal(poll.options.length, 4);
-      assert.deepEqual(poll.voteMode, { holdingVote: {} });
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      assert.equal(vaultBefore - vaultAfter, amount.toNumber());
     });
 
-    it("should fail with invalid question length", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
-
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
+    it("should fail to withdraw by non-admin", async () => {
       try {
         await program.methods
-          .createPoll(
-            "", // Empty question
-            ["Yes", "No"],
-            { fairVote: {} },
-            { binary: {} },
-            60
-          )
+          .withdrawVault(new anchor.BN(1_000_000))
           .accounts({
-            creator: user1.publicKey,
+            admin: user1.publicKey,
             config: configPda,
-            userStats: user1StatsPda,
-            poll: pollPda,
-            tokenMint: mintPda,
-            creatorTokenAccount: user1TokenAccount,
+            solVault: vaultPda,
+            treasury: admin.publicKey,
             systemProgram: SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([user1])
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidQuestionLength");
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
       }
     });
 
-    it("should fail with invalid option count", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
-
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
+    it("should fail to withdraw to a non-treasury account", async () => {
       try {
         await program.methods
-          .createPoll(
-            "Valid question?",
-            ["Only one option"], // Less than 2 options
-            { fairVote: {} },
-            { binary: {} },
-            60
-          )
+          .withdrawVault(new anchor.BN(1_000_000))
           .accounts({
-            creator: user1.publicKey,
+            admin: admin.publicKey,
             config: configPda,
-            userStats: user1StatsPda,
-            poll: pollPda,
-            tokenMint: mintPda,
-            creatorTokenAccount: user1TokenAccount,
+            solVault: vaultPda,
+            treasury: user1.publicKey,
             systemProgram: SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([user1])
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidOptionCount");
+        expect(err.error.errorCode.code).to.equal("InvalidTreasury");
       }
     });
 
-    it("should fail with invalid duration", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
-
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+    it("should fail to withdraw below rent-exempt minimum", async () => {
+      const vaultBalance = await provider.connection.getBalance(vaultPda);
 
       try {
         await program.methods
-          .createPoll(
-            "Valid question?",
-            ["Yes", "No"],
-            { fairVote: {} },
-            { binary: {} },
-            5 // Less than 10 minutes
-          )
+          .withdrawVault(new anchor.BN(vaultBalance))
           .accounts({
-            creator: user1.publicKey,
+            admin: admin.publicKey,
             config: configPda,
-            userStats: user1StatsPda,
-            poll: pollPda,
-            tokenMint: mintPda,
-            creatorTokenAccount: user1TokenAccount,
+            solVault: vaultPda,
+            treasury: admin.publicKey,
             systemProgram: SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InsufficientVaultBalance");
+      }
+    });
+  });
+
+  describe("Pause", () => {
+    it("should fail to pause by non-admin", async () => {
+      try {
+        await program.methods
+          .setPause(true)
+          .accounts({
+            admin: user1.publicKey,
+            config: configPda,
           })
           .signers([user1])
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidDuration");
+        expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
       }
     });
 
-    it("should fail with insufficient tokens", async () => {
-      const config = await program.account.config.fetch(configPda);
-      const pollId = config.totalPollsCreated;
+    it("should reject purchases while paused", async () => {
+      await program.methods
+        .setPause(true)
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
 
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+      let config = await program.account.config.fetch(configPda);
+      assert.equal(config.paused, true);
 
-      // user2 has no tokens
       try {
         await program.methods
-          .createPoll(
-            "Valid question?",
-            ["Yes", "No"],
-            { fairVote: {} },
-            { binary: {} },
-            60
-          )
+          .purchaseTokens(new anchor.BN(1_000_000))
           .accounts({
-            creator: user2.publicKey,
+            buyer: user1.publicKey,
             config: configPda,
-            userStats: user2StatsPda,
-            poll: pollPda,
+            userStats: user1StatsPda,
             tokenMint: mintPda,
-            creatorTokenAccount: user2TokenAccount,
+            buyerTokenAccount: user1TokenAccount,
+            solVault: vaultPda,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([user2])
+          .signers([user1])
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        // Could be InsufficientTokens or constraint error
-        expect(err).to.exist;
+        expect(err.error.errorCode.code).to.equal("ProgramPaused");
       }
+
+      await program.methods
+        .setPause(false)
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      config = await program.account.config.fetch(configPda);
+      assert.equal(config.paused, false);
     });
   });
 
-  describe("Vote", () => {
-    let fairVotePollId: anchor.BN;
-    let holdingVotePollId: anchor.BN;
-
-    before(async () => {
-      // Get poll IDs (poll 0 is FairVote, poll 1 is HoldingVote)
-      fairVotePollId = new anchor.BN(0);
-      holdingVotePollId = new anchor.BN(1);
+  describe("Oracle Pricing", () => {
+    const setOracleParams = async (params: object) => {
+      await program.methods
+        .updateConfig({ ...emptyParams, ...params })
+        .accounts({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+    };
 
-      // Purchase tokens for user2 to vote
-      const amount = new anchor.BN(20_000_000); // 20 mVote
+    const purchaseWithOracle = async (priceOracle: PublicKey | null) => {
       await program.methods
-        .purchaseTokens(amount)
+        .purchaseTokens(new anchor.BN(1_000_000))
         .accounts({
-          buyer: user2.publicKey,
+          buyer: admin.publicKey,
           config: configPda,
-          userStats: user2StatsPda,
+          userStats: adminStatsPda,
           tokenMint: mintPda,
-          buyerTokenAccount: user2TokenAccount,
+          buyerTokenAccount: adminTokenAccount,
           solVault: vaultPda,
+          priceOracle,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([user2])
         .rpc();
+    };
+
+    after(async () => {
+      // Back to the fixed rate for the remaining tests
+      await setOracleParams({
+        priceOracle: PublicKey.default,
+        maxPriceAge: new anchor.BN(60),
+        maxPriceConfBps: 200,
+      });
     });
 
-    it("should vote on FairVote poll successfully", async () => {
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+    it("should fail to set an oracle without a feed id", async () => {
+      try {
+        await setOracleParams({ priceOracle: mockPriceFeed });
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidConfigValue");
+      }
+    });
 
-      const [voteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          fairVotePollId.toArrayLike(Buffer, "le", 8),
-          user2.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
+    it("should fail when the oracle holds a different feed", async () => {
+      await setOracleParams({
+        priceOracle: mockPriceFeed,
+        priceFeedId: Array(32).fill(1),
+      });
 
-      const tx = await program.methods
-        .vote(fairVotePollId, 0, new anchor.BN(1_000_000)) // Vote for option 0 (Yes)
+      try {
+        await purchaseWithOracle(mockPriceFeed);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PriceFeedMismatch");
+      }
+    });
+
+    it("should price purchases from the oracle", async () => {
+      // The mock was published before the program was built and deployed
+      await setOracleParams({
+        priceOracle: mockPriceFeed,
+        priceFeedId: solUsdFeedId,
+        maxPriceAge: new anchor.BN(3600),
+      });
+
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+      await purchaseWithOracle(mockPriceFeed);
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+
+      // 1 mVote = $1 at $200.00/SOL = 0.005 SOL
+      assert.equal(vaultAfter - vaultBefore, 5_000_000);
+    });
+
+    it("should fail without the oracle account", async () => {
+      try {
+        await purchaseWithOracle(null);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidOracleAccount");
+      }
+    });
+
+    it("should fail with a wide confidence interval", async () => {
+      // Mock conf is $0.10 on $200.00 = 5 bps
+      await setOracleParams({ maxPriceConfBps: 1 });
+
+      try {
+        await purchaseWithOracle(mockPriceFeed);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PriceConfidenceTooWide");
+      }
+
+      await setOracleParams({ maxPriceConfBps: 200 });
+    });
+
+    it("should fail with a stale price", async () => {
+      await setOracleParams({
+        priceOracle: stalePriceFeed,
+        priceFeedId: solUsdFeedId,
+      });
+
+      try {
+        await purchaseWithOracle(stalePriceFeed);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("StalePrice");
+      }
+    });
+
+    it("should fail with a price published in the future", async () => {
+      // Published in 2100, which would otherwise look fresh
+      await setOracleParams({ priceOracle: futurePriceFeed });
+
+      try {
+        await purchaseWithOracle(futurePriceFeed);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PriceFromFuture");
+      }
+    });
+  });
+
+  describe("Purchase Tokens With Max Cost", () => {
+    // 1 mVote = $1 at $150.00/SOL = 6_666_666 lamports
+    const amount = new anchor.BN(1_000_000);
+    const solCost = 6_666_666;
+
+    it("should purchase within max lamports", async () => {
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+      await program.methods
+        .purchaseTokensWithMaxCost(amount, new anchor.BN(solCost))
         .accounts({
-          voter: user2.publicKey,
+          buyer: admin.publicKey,
           config: configPda,
-          userStats: user2StatsPda,
-          poll: pollPda,
-          voteRecord: voteRecordPda,
+          userStats: adminStatsPda,
           tokenMint: mintPda,
-          voterTokenAccount: user2TokenAccount,
+          buyerTokenAccount: adminTokenAccount,
+          solVault: vaultPda,
+          priceOracle: null,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([user2])
         .rpc();
 
-      console.log("Vote tx:", tx);
-
-      // Verify poll vote counts
-      const poll = await program.account.poll.fetch(pollPda);
-      assert.equal(poll.voteCounts[0].toNumber(), 1);
-      assert.equal(poll.totalVotes.toNumber(), 1);
-
-      // Verify vote record
-      const voteRecord = await program.account.voteRecord.fetch(voteRecordPda);
-      assert.ok(voteRecord.voter.equals(user2.publicKey));
-      assert.equal(voteRecord.optionIndex, 0);
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
+      assert.equal(vaultAfter - vaultBefore, solCost);
     });
 
-    it("should fail to vote twice on FairVote poll", async () => {
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
-      const [voteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          fairVotePollId.toArrayLike(Buffer, "le", 8),
-          user2.publicKey.toBuffer(),
-        ],
-        program.programId
-      );
-
+    it("should fail when cost exceeds max lamports", async () => {
       try {
         await program.methods
-          .vote(fairVotePollId, 1, new anchor.BN(1_000_000))
+          .purchaseTokensWithMaxCost(amount, new anchor.BN(solCost - 1))
           .accounts({
-            voter: user2.publicKey,
+            buyer: admin.publicKey,
             config: configPda,
-            userStats: user2StatsPda,
-            poll: pollPda,
-            voteRecord: voteRecordPda,
+            userStats: adminStatsPda,
             tokenMint: mintPda,
-            voterTokenAccount: user2TokenAccount,
+            buyerTokenAccount: adminTokenAccount,
+            solVault: vaultPda,
+            priceOracle: null,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([user2])
           .rpc();
-        assert.fail("Should have failed - already voted");
-      } catch (err) {
-        // Expected - vote record already exists
-        expect(err).to.exist;
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("SlippageExceeded");
       }
     });
+  });
 
-    it("should vote on HoldingVote poll with custom amount", async () => {
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), holdingVotePollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
-
-      const [voteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          holdingVotePollId.toArrayLike(Buffer, "le", 8),
-          user2.publicKey.toBuffer(),
-        ],
-        program.programId
+  describe("Redeem Tokens", () => {
+    it("should redeem tokens for SOL minus the spread", async () => {
+      const amount = new anchor.BN(1_000_000); // 1 mVote
+      const tokensBefore = await getAccount(
+        provider.connection,
+        adminTokenAccount
       );
-
-      const tokenAmount = new anchor.BN(5_000_000); // 5 mVote
+      const vaultBefore = await provider.connection.getBalance(vaultPda);
 
       await program.methods
-        .vote(holdingVotePollId, 0, tokenAmount) // Vote for Mobile App
+        .redeemTokens(amount)
         .accounts({
-          voter: user2.publicKey,
+          redeemer: admin.publicKey,
           config: configPda,
-          userStats: user2StatsPda,
-          poll: pollPda,
-          voteRecord: voteRecordPda,
+          userStats: adminStatsPda,
           tokenMint: mintPda,
-          voterTokenAccount: user2TokenAccount,
+          redeemerTokenAccount: adminTokenAccount,
+          solVault: vaultPda,
+          priceOracle: null,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([user2])
         .rpc();
 
-      // Verify poll - votes should equal token amount in HoldingVote mode
-      const poll = await program.account.poll.fetch(pollPda);
-      assert.equal(poll.voteCounts[0].toString(), tokenAmount.toString());
-    });
-
-    it("should fail with invalid option index", async () => {
-      const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
+      const tokensAfter = await getAccount(
+        provider.connection,
+        adminTokenAccount
       );
+      const vaultAfter = await provider.connection.getBalance(vaultPda);
 
-      const [voteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          fairVotePollId.toArrayLike(Buffer, "le", 8),
-          admin.publicKey.toBuffer(),
-        ],
-        program.programId
+      // 6_666_666 lamports at $150.00/SOL minus 5% spread
+      assert.equal(
+        Number(tokensBefore.amount) - Number(tokensAfter.amount),
+        amount.toNumber()
       );
+      assert.equal(vaultBefore - vaultAfter, 6_333_332);
+
+      const stats = await program.account.userStats.fetch(adminStatsPda);
+      assert.equal(stats.tokensRedeemedToday.toNumber(), 1_000_000);
+    });
 
+    it("should fail when exceeding daily redemption limit", async () => {
       try {
         await program.methods
-          .vote(fairVotePollId, 5, new anchor.BN(1_000_000)) // Invalid option
+          .redeemTokens(new anchor.BN(50_000_000)) // 1 + 50 exceeds 50 limit
           .accounts({
-            voter: admin.publicKey,
+            redeemer: admin.publicKey,
             config: configPda,
             userStats: adminStatsPda,
-            poll: pollPda,
-            voteRecord: voteRecordPda,
             tokenMint: mintPda,
-            voterTokenAccount: adminTokenAccount,
+            redeemerTokenAccount: adminTokenAccount,
+            solVault: vaultPda,
+            priceOracle: null,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InvalidOption");
+        expect(err.error.errorCode.code).to.equal("DailyRedeemLimitExceeded");
       }
     });
+  });
 
-    it("should fail with insufficient tokens to vote", async () => {
-      // Create a new user with no tokens but with initialized user_stats
-      const newUser = Keypair.generate();
-      await provider.connection.requestAirdrop(
-        newUser.publicKey,
-        2 * LAMPORTS_PER_SOL
-      );
-      await new Promise((resolve) => setTimeout(resolve, 1000));
-
-      const newUserTokenAccount = getAssociatedTokenAddressSync(
-        mintPda,
-        newUser.publicKey
-      );
+  describe("Create Poll", () => {
+    before(async () => {
+      // Purchase tokens for user1
+      const amount = new anchor.BN(50_000_000); // 50 mVote
+      await program.methods
+        .purchaseTokens(amount)
+        .accounts({
+          buyer: user1.publicKey,
+          config: configPda,
+          userStats: user1StatsPda,
+          tokenMint: mintPda,
+          buyerTokenAccount: user1TokenAccount,
+          solVault: vaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1])
+        .rpc();
+    });
 
-      // Create ATA
-      const createAtaIx = createAssociatedTokenAccountInstruction(
-        newUser.publicKey,
-        newUserTokenAccount,
-        newUser.publicKey,
-        mintPda
-      );
-      const tx = new anchor.web3.Transaction().add(createAtaIx);
-      await provider.sendAndConfirm(tx, [newUser]);
+    it("should create a binary poll (FairVote)", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
 
-      const [newUserStatsPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("user"), newUser.publicKey.toBuffer()],
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
-      // Purchase and spend tokens to initialize user_stats but end up with 0 balance
-      // Buy 1 mVote first
-      await program.methods
-        .purchaseTokens(new anchor.BN(1_000_000))
+      const tx = await program.methods
+        .createPoll(
+          "Should we implement dark mode?",
+          ["Yes", "No"],
+          { fairVote: {} },
+          { binary: {} },
+          60, // 60 minutes
+          null,
+          null
+        )
         .accounts({
-          buyer: newUser.publicKey,
+          creator: user1.publicKey,
           config: configPda,
-          userStats: newUserStatsPda,
+          userStats: user1StatsPda,
+          poll: pollPda,
           tokenMint: mintPda,
-          buyerTokenAccount: newUserTokenAccount,
-          solVault: vaultPda,
+          creatorTokenAccount: user1TokenAccount,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([newUser])
+        .signers([user1])
         .rpc();
 
-      // Vote on holding vote poll to spend the token
-      const [holdingPollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), holdingVotePollId.toArrayLike(Buffer, "le", 8)],
-        program.programId
-      );
+      console.log("Create poll tx:", tx);
 
-      const [holdingVoteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          holdingVotePollId.toArrayLike(Buffer, "le", 8),
-          newUser.publicKey.toBuffer(),
-        ],
+      // Verify poll
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.question, "Should we implement dark mode?");
+      assert.equal(poll.options.length, 2);
+      assert.deepEqual(poll.voteMode, { fairVote: {} });
+      assert.deepEqual(poll.status, { active: {} });
+    });
+
+    it("should create a multiple choice poll (HoldingVote)", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       await program.methods
-        .vote(holdingVotePollId, 1, new anchor.BN(1_000_000))
+        .createPoll(
+          "What feature should we build next?",
+          ["Mobile App", "API Access", "Dark Mode", "Notifications"],
+          { holdingVote: {} },
+          { multipleChoice: {} },
+          120, // 2 hours
+          null,
+          null
+        )
         .accounts({
-          voter: newUser.publicKey,
+          creator: user1.publicKey,
           config: configPda,
-          userStats: newUserStatsPda,
-          poll: holdingPollPda,
-          voteRecord: holdingVoteRecordPda,
+          userStats: user1StatsPda,
+          poll: pollPda,
           tokenMint: mintPda,
-          voterTokenAccount: newUserTokenAccount,
+          creatorTokenAccount: user1TokenAccount,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
-        .signers([newUser])
+        .signers([user1])
         .rpc();
 
-      // Now try to vote on fair vote poll with 0 tokens
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.options.length, 4);
+      assert.deepEqual(poll.voteMode, { holdingVote: {} });
+    });
+
+    it("should fail with invalid question length", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
       const [pollPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
-      const [voteRecordPda] = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("vote"),
-          fairVotePollId.toArrayLike(Buffer, "le", 8),
-          newUser.publicKey.toBuffer(),
-        ],
+      try {
+        await program.methods
+          .createPoll(
+            "", // Empty question
+            ["Yes", "No"],
+            { fairVote: {} },
+            { binary: {} },
+            60,
+            null,
+            null
+          )
+          .accounts({
+            creator: user1.publicKey,
+            config: configPda,
+            userStats: user1StatsPda,
+            poll: pollPda,
+            tokenMint: mintPda,
+            creatorTokenAccount: user1TokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidQuestionLength");
+      }
+    });
+
+    it("should fail with invalid option count", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
         program.programId
       );
 
       try {
         await program.methods
-          .vote(fairVotePollId, 0, new anchor.BN(1_000_000))
+          .createPoll(
+            "Valid question?",
+            ["Only one option"], // Less than 2 options
+            { fairVote: {} },
+            { binary: {} },
+            60,
+            null,
+            null
+          )
           .accounts({
-            voter: newUser.publicKey,
+            creator: user1.publicKey,
             config: configPda,
-            userStats: newUserStatsPda,
+            userStats: user1StatsPda,
             poll: pollPda,
-            voteRecord: voteRecordPda,
             tokenMint: mintPda,
-            voterTokenAccount: newUserTokenAccount,
+            creatorTokenAccount: user1TokenAccount,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
-          .signers([newUser])
+          .signers([user1])
           .rpc();
         assert.fail("Should have failed");
       } catch (err: any) {
-        expect(err.error.errorCode.code).to.equal("InsufficientTokens");
+        expect(err.error.errorCode.code).to.equal("InvalidOptionCount");
+      }
+    });
+
+    it("should fail with invalid duration", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createPoll(
+            "Valid question?",
+            ["Yes", "No"],
+            { fairVote: {} },
+            { binary: {} },
+            5, // Less than 10 minutes
+            null,
+            null
+          )
+          .accounts({
+            creator: user1.publicKey,
+            config: configPda,
+            userStats: user1StatsPda,
+            poll: pollPda,
+            tokenMint: mintPda,
+            creatorTokenAccount: user1TokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDuration");
+      }
+    });
+
+    it("should fail with insufficient tokens", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      // user2 has no tokens
+      try {
+        await program.methods
+          .createPoll(
+            "Valid question?",
+            ["Yes", "No"],
+            { fairVote: {} },
+            { binary: {} },
+            60,
+            null,
+            null
+          )
+          .accounts({
+            creator: user2.publicKey,
+            config: configPda,
+            userStats: user2StatsPda,
+            poll: pollPda,
+            tokenMint: mintPda,
+            creatorTokenAccount: user2TokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        // Could be InsufficientTokens or constraint error
+        expect(err).to.exist;
       }
     });
   });
 
+  describe("Vote", () => {
+    let fairVotePollId: anchor.BN;
+    let holdingVotePollId: anchor.BN;
+
+    before(async () => {
+      // Get poll IDs (poll 0 is FairVote, poll 1 is HoldingVote)
+      fairVotePollId = new anchor.BN(0);
+      holdingVotePollId = new anchor.BN(1);
+
+      // Purchase tokens for user2 to vote
+      const amount = new anchor.BN(20_000_000); // 20 mVote
+      await program.methods
+        .purchaseTokens(amount)
+        .accounts({
+          buyer: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          tokenMint: mintPda,
+          buyerTokenAccount: user2TokenAccount,
+          solVault: vaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+    });
+
+    it("should vote on FairVote poll successfully", async () => {
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          fairVotePollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const tx = await program.methods
+        .vote(fairVotePollId, 0, new anchor.BN(1_000_000)) // Vote for option 0 (Yes)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      console.log("Vote tx:", tx);
+
+      // Verify poll vote counts
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[0].toNumber(), 1);
+      assert.equal(poll.totalVotes.toNumber(), 1);
+
+      // Verify vote record
+      const voteRecord = await program.account.voteRecord.fetch(voteRecordPda);
+      assert.ok(voteRecord.voter.equals(user2.publicKey));
+      assert.equal(voteRecord.optionIndex, 0);
+    });
+
+    it("should fail to vote twice on FairVote poll", async () => {
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          fairVotePollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .vote(fairVotePollId, 1, new anchor.BN(1_000_000))
+          .accounts({
+            voter: user2.publicKey,
+            config: configPda,
+            userStats: user2StatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+            tokenMint: mintPda,
+            voterTokenAccount: user2TokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed - already voted");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyVoted");
+      }
+    });
+
+    it("should vote on HoldingVote poll with custom amount", async () => {
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), holdingVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          holdingVotePollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const tokenAmount = new anchor.BN(5_000_000); // 5 mVote
+
+      await program.methods
+        .vote(holdingVotePollId, 0, tokenAmount) // Vote for Mobile App
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      // Verify poll - votes should equal token amount in HoldingVote mode
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[0].toString(), tokenAmount.toString());
+    });
+
+    it("should add to an existing HoldingVote vote", async () => {
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), holdingVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          holdingVotePollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const tokenAmount = new anchor.BN(3_000_000); // 3 mVote
+
+      await program.methods
+        .vote(holdingVotePollId, 1, tokenAmount) // Vote for API Access
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      // Verify accumulated vote record and per-option breakdown
+      const voteRecord = await program.account.voteRecord.fetch(voteRecordPda);
+      assert.equal(voteRecord.voteCount, 2);
+      assert.equal(voteRecord.tokensSpent.toNumber(), 8_000_000);
+      assert.equal(voteRecord.optionTokens[0].toNumber(), 5_000_000);
+      assert.equal(voteRecord.optionTokens[1].toNumber(), 3_000_000);
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[1].toString(), tokenAmount.toString());
+      assert.equal(poll.totalVotes.toNumber(), 2);
+    });
+
+    it("should fail with invalid option index", async () => {
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          fairVotePollId.toArrayLike(Buffer, "le", 8),
+          admin.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .vote(fairVotePollId, 5, new anchor.BN(1_000_000)) // Invalid option
+          .accounts({
+            voter: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+            tokenMint: mintPda,
+            voterTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidOption");
+      }
+    });
+
+    it("should fail with insufficient tokens to vote", async () => {
+      // Create a new user with no tokens but with initialized user_stats
+      const newUser = Keypair.generate();
+      await provider.connection.requestAirdrop(
+        newUser.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+
+      const newUserTokenAccount = getAssociatedTokenAddressSync(
+        mintPda,
+        newUser.publicKey
+      );
+
+      // Create ATA
+      const createAtaIx = createAssociatedTokenAccountInstruction(
+        newUser.publicKey,
+        newUserTokenAccount,
+        newUser.publicKey,
+        mintPda
+      );
+      const tx = new anchor.web3.Transaction().add(createAtaIx);
+      await provider.sendAndConfirm(tx, [newUser]);
+
+      const [newUserStatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), newUser.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Purchase and spend tokens to initialize user_stats but end up with 0 balance
+      // Buy 1 mVote first
+      await program.methods
+        .purchaseTokens(new anchor.BN(1_000_000))
+        .accounts({
+          buyer: newUser.publicKey,
+          config: configPda,
+          userStats: newUserStatsPda,
+          tokenMint: mintPda,
+          buyerTokenAccount: newUserTokenAccount,
+          solVault: vaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([newUser])
+        .rpc();
+
+      // Vote on holding vote poll to spend the token
+      const [holdingPollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), holdingVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [holdingVoteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          holdingVotePollId.toArrayLike(Buffer, "le", 8),
+          newUser.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .vote(holdingVotePollId, 1, new anchor.BN(1_000_000))
+        .accounts({
+          voter: newUser.publicKey,
+          config: configPda,
+          userStats: newUserStatsPda,
+          poll: holdingPollPda,
+          voteRecord: holdingVoteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: newUserTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([newUser])
+        .rpc();
+
+      // Now try to vote on fair vote poll with 0 tokens
+      const [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), fairVotePollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          fairVotePollId.toArrayLike(Buffer, "le", 8),
+          newUser.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .vote(fairVotePollId, 0, new anchor.BN(1_000_000))
+          .accounts({
+            voter: newUser.publicKey,
+            config: configPda,
+            userStats: newUserStatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+            tokenMint: mintPda,
+            voterTokenAccount: newUserTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([newUser])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InsufficientTokens");
+      }
+    });
+  });
+
+  describe("Change And Retract Vote", () => {
+    // user2 voted for option 0 on the FairVote poll created by user1
+    const pollId = new anchor.BN(0);
+    let pollPda: PublicKey;
+    let voteRecordPda: PublicKey;
+
+    before(() => {
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          pollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+    });
+
+    it("should fail to change vote before the creator enables changes", async () => {
+      try {
+        await program.methods
+          .changeVote(pollId, 1)
+          .accounts({
+            voter: user2.publicKey,
+            config: configPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("VoteChangesDisabled");
+      }
+
+      await program.methods
+        .setVoteChanges(pollId, true)
+        .accounts({
+          creator: user1.publicKey,
+          poll: pollPda,
+        })
+        .signers([user1])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.isTrue(poll.allowVoteChanges);
+    });
+
+    it("should change vote to another option", async () => {
+      const pollBefore = await program.account.poll.fetch(pollPda);
+
+      await program.methods
+        .changeVote(pollId, 1)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+        })
+        .signers([user2])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(
+        poll.voteCounts[0].toNumber(),
+        pollBefore.voteCounts[0].toNumber() - 1
+      );
+      assert.equal(
+        poll.voteCounts[1].toNumber(),
+        pollBefore.voteCounts[1].toNumber() + 1
+      );
+      assert.equal(poll.totalVotes.toNumber(), pollBefore.totalVotes.toNumber());
+
+      const voteRecord = await program.account.voteRecord.fetch(voteRecordPda);
+      assert.equal(voteRecord.optionIndex, 1);
+    });
+
+    it("should fail to change vote to invalid option", async () => {
+      try {
+        await program.methods
+          .changeVote(pollId, 5)
+          .accounts({
+            voter: user2.publicKey,
+            config: configPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidOption");
+      }
+    });
+
+    it("should fail to set vote changes by non-creator", async () => {
+      try {
+        await program.methods
+          .setVoteChanges(pollId, false)
+          .accounts({
+            creator: user2.publicKey,
+            poll: pollPda,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+    });
+
+    it("should fail to change vote when disabled by creator", async () => {
+      await program.methods
+        .setVoteChanges(pollId, false)
+        .accounts({
+          creator: user1.publicKey,
+          poll: pollPda,
+        })
+        .signers([user1])
+        .rpc();
+
+      try {
+        await program.methods
+          .changeVote(pollId, 0)
+          .accounts({
+            voter: user2.publicKey,
+            config: configPda,
+            poll: pollPda,
+            voteRecord: voteRecordPda,
+          })
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("VoteChangesDisabled");
+      }
+
+      await program.methods
+        .setVoteChanges(pollId, true)
+        .accounts({
+          creator: user1.publicKey,
+          poll: pollPda,
+        })
+        .signers([user1])
+        .rpc();
+    });
+
+    it("should retract vote and close the vote record", async () => {
+      const pollBefore = await program.account.poll.fetch(pollPda);
+      const recordBefore = await program.account.voteRecord.fetch(
+        voteRecordPda
+      );
+      const statsBefore = await program.account.userStats.fetch(user2StatsPda);
+
+      await program.methods
+        .retractVote(pollId)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+        })
+        .signers([user2])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(
+        poll.voteCounts[1].toNumber(),
+        pollBefore.voteCounts[1].toNumber() - 1
+      );
+      assert.equal(
+        poll.totalVotes.toNumber(),
+        pollBefore.totalVotes.toNumber() - 1
+      );
+      assert.equal(
+        poll.totalTokensSpent.toNumber(),
+        pollBefore.totalTokensSpent.toNumber() -
+          recordBefore.tokensSpent.toNumber()
+      );
+
+      const stats = await program.account.userStats.fetch(user2StatsPda);
+      assert.equal(
+        stats.votesCast.toNumber(),
+        statsBefore.votesCast.toNumber() - recordBefore.voteCount
+      );
+
+      const voteRecord = await program.account.voteRecord.fetchNullable(
+        voteRecordPda
+      );
+      assert.isNull(voteRecord);
+    });
+  });
+
+  describe("Escrow Vote", () => {
+    let escrowPollId: anchor.BN;
+    let pollPda: PublicKey;
+    let voteRecordPda: PublicKey;
+    let escrowPda: PublicKey;
+    const tokenAmount = new anchor.BN(2_000_000); // 2 mVote
+
+    const reclaimAccounts = () => ({
+      voter: user2.publicKey,
+      config: configPda,
+      poll: pollPda,
+      voteRecord: voteRecordPda,
+      tokenMint: mintPda,
+      escrowTokenAccount: escrowPda,
+      voterTokenAccount: user2TokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+
+    before(async () => {
+      const config = await program.account.config.fetch(configPda);
+      escrowPollId = config.totalPollsCreated;
+
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), escrowPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          escrowPollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      [escrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), escrowPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createPoll(
+          "Escrowed conviction poll?",
+          ["Yes", "No"],
+          { escrowVote: {} },
+          { binary: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: adminTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("should escrow tokens instead of burning them", async () => {
+      const supplyBefore = (await provider.connection.getTokenSupply(mintPda))
+        .value.amount;
+
+      await program.methods
+        .vote(escrowPollId, 0, tokenAmount)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          escrowTokenAccount: escrowPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      const escrow = await getAccount(provider.connection, escrowPda);
+      assert.equal(escrow.amount.toString(), tokenAmount.toString());
+
+      const supplyAfter = (await provider.connection.getTokenSupply(mintPda))
+        .value.amount;
+      assert.equal(supplyAfter, supplyBefore);
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[0].toString(), tokenAmount.toString());
+    });
+
+    it("should fail to reclaim while poll is active", async () => {
+      try {
+        await program.methods
+          .reclaimVoteTokens(escrowPollId)
+          .accounts(reclaimAccounts())
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollStillActive");
+      }
+    });
+
+    it("should reclaim escrowed tokens after poll is closed", async () => {
+      await program.methods
+        .closePoll(escrowPollId)
+        .accounts({
+          creator: admin.publicKey,
+          poll: pollPda,
+        })
+        .rpc();
+
+      const before = await getAccount(provider.connection, user2TokenAccount);
+
+      await program.methods
+        .reclaimVoteTokens(escrowPollId)
+        .accounts(reclaimAccounts())
+        .signers([user2])
+        .rpc();
+
+      const after = await getAccount(provider.connection, user2TokenAccount);
+      assert.equal(
+        Number(after.amount) - Number(before.amount),
+        tokenAmount.toNumber()
+      );
+
+      const voteRecord = await program.account.voteRecord.fetch(voteRecordPda);
+      assert.equal(voteRecord.tokensReclaimed, true);
+    });
+
+    it("should fail to reclaim twice", async () => {
+      try {
+        await program.methods
+          .reclaimVoteTokens(escrowPollId)
+          .accounts(reclaimAccounts())
+          .signers([user2])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TokensAlreadyReclaimed");
+      }
+    });
+  });
+
+  describe("Quadratic Vote", () => {
+    let quadraticPollId: anchor.BN;
+    let pollPda: PublicKey;
+    let voteRecordPda: PublicKey;
+
+    const castVote = async (tokenAmount: anchor.BN) => {
+      await program.methods
+        .vote(quadraticPollId, 0, tokenAmount)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+    };
+
+    before(async () => {
+      const config = await program.account.config.fetch(configPda);
+      quadraticPollId = config.totalPollsCreated;
+
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), quadraticPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          quadraticPollId.toArrayLike(Buffer, "le", 8),
+          user2.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .createPoll(
+          "Quadratic poll?",
+          ["Yes", "No"],
+          { quadratic: {} },
+          { binary: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: adminTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("should weight votes by the square root of tokens", async () => {
+      await castVote(new anchor.BN(1_000_000)); // sqrt(1_000_000) = 1000
+
+      let poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[0].toNumber(), 1000);
+
+      // Adding 3 mVote brings the option total to 4 mVote: sqrt = 2000
+      await castVote(new anchor.BN(3_000_000));
+
+      poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.voteCounts[0].toNumber(), 2000);
+      assert.equal(poll.totalTokensSpent.toNumber(), 4_000_000);
+    });
+
+    it("should fail below the minimum vote cost", async () => {
+      try {
+        await castVote(new anchor.BN(999_999));
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("MinimumVoteRequired");
+      }
+    });
+  });
+
+  describe("Ranked Choice", () => {
+    let rankedPollId: anchor.BN;
+    let pollPda: PublicKey;
+    let ballotsPda: PublicKey;
+    const voters: Keypair[] = [];
+
+    const voteRecordFor = (voter: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          rankedPollId.toArrayLike(Buffer, "le", 8),
+          voter.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    const castRanked = async (voter: Keypair, rankings: number[]) => {
+      const [statsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), voter.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .voteRanked(rankedPollId, Buffer.from(rankings))
+        .accounts({
+          voter: voter.publicKey,
+          config: configPda,
+          userStats: statsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(voter.publicKey),
+          rankedBallots: ballotsPda,
+          tokenMint: mintPda,
+          voterTokenAccount: getAssociatedTokenAddressSync(
+            mintPda,
+            voter.publicKey
+          ),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter])
+        .rpc();
+    };
+
+    before(async () => {
+      const config = await program.account.config.fetch(configPda);
+      rankedPollId = config.totalPollsCreated;
+
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), rankedPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [ballotsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("ballots"), rankedPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createPoll(
+          "Ranked poll?",
+          ["Alpha", "Beta", "Gamma"],
+          { fairVote: {} },
+          { rankedChoice: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: adminTokenAccount,
+          rankedBallots: ballotsPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      // Five fresh voters with 1 mVote each
+      for (let i = 0; i < 5; i++) {
+        voters.push((await createFundedUser(1_000_000)).user);
+      }
+    });
+
+    it("should fail to create a ranked poll with HoldingVote", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      try {
+        await program.methods
+          .createPoll(
+            "Ranked holding poll?",
+            ["Alpha", "Beta"],
+            { holdingVote: {} },
+            { rankedChoice: {} },
+            60,
+            null,
+            null
+          )
+          .accounts({
+            creator: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: PublicKey.findProgramAddressSync(
+              [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+              program.programId
+            )[0],
+            tokenMint: mintPda,
+            creatorTokenAccount: adminTokenAccount,
+            rankedBallots: PublicKey.findProgramAddressSync(
+              [Buffer.from("ballots"), pollId.toArrayLike(Buffer, "le", 8)],
+              program.programId
+            )[0],
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal(
+          "RankedChoiceRequiresFairVote"
+        );
+      }
+    });
+
+    it("should cast ranked ballots", async () => {
+      await castRanked(voters[0], [0]);
+      await castRanked(voters[1], [0, 1]);
+      await castRanked(voters[2], [1]);
+      await castRanked(voters[3], [1, 0]);
+      await castRanked(voters[4], [2, 1]);
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [2, 2, 1]
+      );
+
+      const voteRecord = await program.account.voteRecord.fetch(
+        voteRecordFor(voters[4].publicKey)
+      );
+      assert.deepEqual(Array.from(voteRecord.rankings), [2, 1]);
+    });
+
+    it("should fail with duplicate rankings", async () => {
+      try {
+        await castRanked(admin.payer, [0, 0]);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidRankings");
+      }
+    });
+
+    it("should fail to cast a single-option vote on a ranked poll", async () => {
+      try {
+        await program.methods
+          .vote(rankedPollId, 0, new anchor.BN(1_000_000))
+          .accounts({
+            voter: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordFor(admin.publicKey),
+            tokenMint: mintPda,
+            voterTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RankedBallotRequired");
+      }
+    });
+
+    it("should fail to tally while poll is active", async () => {
+      try {
+        await program.methods
+          .tallyRanked(rankedPollId)
+          .accounts({
+            caller: user1.publicKey,
+            poll: pollPda,
+            rankedBallots: ballotsPda,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollStillActive");
+      }
+    });
+
+    it("should tally by instant-runoff after poll is closed", async () => {
+      await program.methods
+        .closePoll(rankedPollId)
+        .accounts({
+          creator: admin.publicKey,
+          poll: pollPda,
+        })
+        .rpc();
+
+      // Anyone can run the tally
+      await program.methods
+        .tallyRanked(rankedPollId)
+        .accounts({
+          caller: user1.publicKey,
+          poll: pollPda,
+          rankedBallots: ballotsPda,
+        })
+        .signers([user1])
+        .rpc();
+
+      // Round 1: 2-2-1, Gamma eliminated; round 2: Gamma's ballot moves to Beta
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.isTallied, true);
+      assert.equal(poll.winningOption, 1);
+      assert.deepEqual(
+        poll.tallyRounds.map((round) => round.map((count) => count.toNumber())),
+        [
+          [2, 2, 1, 0],
+          [2, 3, 0, 0],
+        ]
+      );
+    });
+
+    it("should fail to tally twice", async () => {
+      try {
+        await program.methods
+          .tallyRanked(rankedPollId)
+          .accounts({
+            caller: user1.publicKey,
+            poll: pollPda,
+            rankedBallots: ballotsPda,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyTallied");
+      }
+    });
+
+    it("should finalize with the instant-runoff winner", async () => {
+      await program.methods
+        .finalizePoll(rankedPollId)
+        .accounts({
+          caller: user1.publicKey,
+          poll: pollPda,
+        })
+        .signers([user1])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.deepEqual(poll.status, { finalized: {} });
+      assert.equal(poll.winningOption, 1);
+      assert.equal(poll.isTie, false);
+    });
+  });
+
+  describe("Approval Vote", () => {
+    let approvalPollId: anchor.BN;
+    let pollPda: PublicKey;
+
+    const voteRecordFor = (voter: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          approvalPollId.toArrayLike(Buffer, "le", 8),
+          voter.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    before(async () => {
+      const config = await program.account.config.fetch(configPda);
+      approvalPollId = config.totalPollsCreated;
+
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), approvalPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      await program.methods
+        .createPoll(
+          "Which agenda items?",
+          ["Budget", "Hiring", "Roadmap"],
+          { fairVote: {} },
+          { approval: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: adminTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      await program.methods
+        .setVoteChanges(approvalPollId, true)
+        .accounts({
+          creator: admin.publicKey,
+          poll: pollPda,
+        })
+        .rpc();
+    });
+
+    it("should fail to create an approval poll with HoldingVote", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const [holdingPollPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("poll"),
+          config.totalPollsCreated.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .createPoll(
+            "Approval holding poll?",
+            ["Budget", "Hiring"],
+            { holdingVote: {} },
+            { approval: {} },
+            60,
+            null,
+            null
+          )
+          .accounts({
+            creator: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: holdingPollPda,
+            tokenMint: mintPda,
+            creatorTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ApprovalRequiresFairVote");
+      }
+    });
+
+    it("should approve several options for one vote cost", async () => {
+      const balanceBefore = await getAccount(
+        provider.connection,
+        user2TokenAccount
+      );
+
+      await program.methods
+        .voteApproval(approvalPollId, 0b101)
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(user2.publicKey),
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      await program.methods
+        .voteApproval(approvalPollId, 0b011)
+        .accounts({
+          voter: user1.publicKey,
+          config: configPda,
+          userStats: user1StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(user1.publicKey),
+          tokenMint: mintPda,
+          voterTokenAccount: user1TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [2, 1, 1]
+      );
+      assert.equal(poll.totalVotes.toNumber(), 2);
+
+      const voteRecord = await program.account.voteRecord.fetch(
+        voteRecordFor(user2.publicKey)
+      );
+      assert.equal(voteRecord.optionMask, 0b101);
+      assert.equal(voteRecord.tokensSpent.toNumber(), 1_000_000);
+
+      const balanceAfter = await getAccount(
+        provider.connection,
+        user2TokenAccount
+      );
+      assert.equal(
+        Number(balanceBefore.amount) - Number(balanceAfter.amount),
+        1_000_000
+      );
+    });
+
+    it("should fail with an option mask past the last option", async () => {
+      try {
+        await program.methods
+          .voteApproval(approvalPollId, 0b1000)
+          .accounts({
+            voter: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordFor(admin.publicKey),
+            tokenMint: mintPda,
+            voterTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidOptionMask");
+      }
+    });
+
+    it("should fail to cast a single-option vote on an approval poll", async () => {
+      try {
+        await program.methods
+          .vote(approvalPollId, 0, new anchor.BN(1_000_000))
+          .accounts({
+            voter: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordFor(admin.publicKey),
+            tokenMint: mintPda,
+            voterTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ApprovalBallotRequired");
+      }
+    });
+
+    it("should remove every approved option when retracting", async () => {
+      await program.methods
+        .retractVote(approvalPollId)
+        .accounts({
+          voter: user1.publicKey,
+          config: configPda,
+          userStats: user1StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(user1.publicKey),
+        })
+        .signers([user1])
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [1, 0, 1]
+      );
+      assert.equal(poll.totalVotes.toNumber(), 1);
+    });
+  });
+
+  describe("Secret Ballot", () => {
+    let secretPollId: anchor.BN;
+    let pollPda: PublicKey;
+    const user2Salt = randomBytes(32);
+    const adminSalt = randomBytes(32);
+
+    // sha256(option_index || salt || voter)
+    const commitmentFor = (option: number, salt: Buffer, voter: PublicKey) =>
+      Array.from(
+        createHash("sha256")
+          .update(Buffer.from([option]))
+          .update(salt)
+          .update(voter.toBuffer())
+          .digest()
+      );
+
+    const voteRecordFor = (voter: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          secretPollId.toArrayLike(Buffer, "le", 8),
+          voter.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    const revealAs = (voter: Keypair, option: number, salt: Buffer) =>
+      program.methods
+        .revealVote(secretPollId, option, Array.from(salt))
+        .accounts({
+          voter: voter.publicKey,
+          poll: pollPda,
+          voteRecord: voteRecordFor(voter.publicKey),
+        })
+        .signers([voter])
+        .rpc();
+
+    before(async () => {
+      const config = await program.account.config.fetch(configPda);
+      secretPollId = config.totalPollsCreated;
+
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), secretPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+    });
+
+    it("should fail to create a secret poll with a short reveal window", async () => {
+      try {
+        await program.methods
+          .createSecretPoll(
+            "Secret poll?",
+            ["Yes", "No"],
+            { binary: {} },
+            60,
+            5,
+            null,
+            null
+          )
+          .accounts({
+            creator: admin.publicKey,
+            config: configPda,
+            userStats: adminStatsPda,
+            poll: pollPda,
+            tokenMint: mintPda,
+            creatorTokenAccount: adminTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidRevealDuration");
+      }
+    });
+
+    it("should create a secret poll", async () => {
+      await program.methods
+        .createSecretPoll(
+          "Secret poll?",
+          ["Yes", "No"],
+          { binary: {} },
+          60,
+          30,
+          null,
+          null
+        )
+        .accounts({
+          creator: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: adminTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.isSecret, true);
+      assert.equal(
+        poll.revealEndTime.toNumber(),
+        poll.endTime.toNumber() + 30 * 60
+      );
+    });
+
+    it("should commit votes without updating counts", async () => {
+      await program.methods
+        .commitVote(
+          secretPollId,
+          commitmentFor(1, user2Salt, user2.publicKey)
+        )
+        .accounts({
+          voter: user2.publicKey,
+          config: configPda,
+          userStats: user2StatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(user2.publicKey),
+          tokenMint: mintPda,
+          voterTokenAccount: user2TokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+
+      await program.methods
+        .commitVote(
+          secretPollId,
+          commitmentFor(0, adminSalt, admin.publicKey)
+        )
+        .accounts({
+          voter: admin.publicKey,
+          config: configPda,
+          userStats: adminStatsPda,
+          poll: pollPda,
+          voteRecord: voteRecordFor(admin.publicKey),
+          tokenMint: mintPda,
+          voterTokenAccount: adminTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.equal(poll.committedVotes.toNumber(), 2);
+      assert.equal(poll.totalVotes.toNumber(), 0);
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [0, 0]
+      );
+    });
+
+    it("should fail to cast a plain vote on a secret poll", async () => {
+      try {
+        await program.methods
+          .vote(secretPollId, 0, new anchor.BN(1_000_000))
+          .accounts({
+            voter: user1.publicKey,
+            config: configPda,
+            userStats: user1StatsPda,
+            poll: pollPda,
+            voteRecord: voteRecordFor(user1.publicKey),
+            tokenMint: mintPda,
+            voterTokenAccount: user1TokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommitmentRequired");
+      }
+    });
+
+    it("should fail to reveal while voting is open", async () => {
+      try {
+        await revealAs(user2, 1, user2Salt);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RevealWindowClosed");
+      }
+    });
+
+    it("should fail to reveal with the wrong salt", async () => {
+      await program.methods
+        .closePoll(secretPollId)
+        .accounts({
+          creator: admin.publicKey,
+          poll: pollPda,
+        })
+        .rpc();
+
+      try {
+        await revealAs(user2, 1, adminSalt);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("CommitmentMismatch");
+      }
+    });
+
+    it("should add revealed votes and report unrevealed ones", async () => {
+      await revealAs(user2, 1, user2Salt);
+
+      const poll = await program.account.poll.fetch(pollPda);
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [0, 1]
+      );
+      assert.equal(poll.totalVotes.toNumber(), 1);
+      assert.equal(poll.revealedVotes.toNumber(), 1);
+      assert.equal(
+        poll.committedVotes.toNumber() - poll.revealedVotes.toNumber(),
+        1
+      );
+
+      const voteRecord = await program.account.voteRecord.fetch(
+        voteRecordFor(user2.publicKey)
+      );
+      assert.equal(voteRecord.isRevealed, true);
+      assert.equal(voteRecord.optionIndex, 1);
+    });
+
+    it("should fail to reveal twice", async () => {
+      try {
+        await revealAs(user2, 1, user2Salt);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyRevealed");
+      }
+    });
+
+    it("should fail to finalize during the reveal window", async () => {
+      try {
+        await program.methods
+          .finalizePoll(secretPollId)
+          .accounts({
+            caller: user1.publicKey,
+            poll: pollPda,
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RevealWindowOpen");
+      }
+    });
+  });
+
+  describe("Scheduled Poll", () => {
+    let creator: Keypair;
+    let creatorTokenAccount: PublicKey;
+    let creatorStatsPda: PublicKey;
+
+    const pollPdaFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const chainTime = async () =>
+      provider.connection.getBlockTime(await provider.connection.getSlot());
+
+    const createScheduledPoll = async (startTime: number) => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      await program.methods
+        .createPoll(
+          "Scheduled poll?",
+          ["Yes", "No"],
+          { fairVote: {} },
+          { binary: {} },
+          60,
+          new anchor.BN(startTime),
+          null
+        )
+        .accounts({
+          creator: creator.publicKey,
+          config: configPda,
+          userStats: creatorStatsPda,
+          poll: pollPdaFor(pollId),
+          tokenMint: mintPda,
+          creatorTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      return pollId;
+    };
+
+    before(async () => {
+      ({
+        user: creator,
+        tokenAccount: creatorTokenAccount,
+        statsPda: creatorStatsPda,
+      } = await createFundedUser(20_000_000));
+    });
+
+    it("should create a poll scheduled to start later", async () => {
+      const startTime = (await chainTime()) + 3600;
+      const pollId = await createScheduledPoll(startTime);
+
+      const poll = await program.account.poll.fetch(pollPdaFor(pollId));
+      assert.equal(poll.startTime.toNumber(), startTime);
+      assert.equal(poll.endTime.toNumber(), startTime + 60 * 60);
+      assert.deepEqual(poll.status, { scheduled: {} });
+    });
+
+    it("should fail to vote before the poll starts", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated.subn(1);
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          pollId.toArrayLike(Buffer, "le", 8),
+          creator.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .vote(pollId, 0, new anchor.BN(1_000_000))
+          .accounts({
+            voter: creator.publicKey,
+            config: configPda,
+            userStats: creatorStatsPda,
+            poll: pollPdaFor(pollId),
+            voteRecord: voteRecordPda,
+            tokenMint: mintPda,
+            voterTokenAccount: creatorTokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollNotStarted");
+      }
+    });
+
+    it("should fail to schedule a poll past the max lead time", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const startTime =
+        (await chainTime()) + config.maxStartLeadTime.toNumber() + 3600;
+
+      try {
+        await createScheduledPoll(startTime);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidStartTime");
+      }
+    });
+
+    it("should fail to schedule a poll in the past", async () => {
+      try {
+        await createScheduledPoll((await chainTime()) - 3600);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidStartTime");
+      }
+    });
+
+    it("should fail to set a max lead time above the bound", async () => {
+      try {
+        await program.methods
+          .updateConfig({
+            ...emptyParams,
+            maxStartLeadTime: new anchor.BN(365 * 86400),
+          })
+          .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidConfigValue");
+      }
+    });
+  });
+
+  describe("Finalize Poll", () => {
+    type FundedUser = Awaited<ReturnType<typeof createFundedUser>>;
+
+    let creator: FundedUser;
+    let voter: FundedUser;
+    let winnerPollId: anchor.BN;
+    let tiePollId: anchor.BN;
+    let quorumPollId: anchor.BN;
+
+    const pollPdaFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const createBinaryPoll = async (passRules: any) => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      await program.methods
+        .createPoll(
+          "Poll to be finalized?",
+          ["Yes", "No"],
+          { fairVote: {} },
+          { binary: {} },
+          60,
+          null,
+          passRules
+        )
+        .accounts({
+          creator: creator.user.publicKey,
+          config: configPda,
+          userStats: creator.statsPda,
+          poll: pollPdaFor(pollId),
+          tokenMint: mintPda,
+          creatorTokenAccount: creator.tokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator.user])
+        .rpc();
+
+      return pollId;
+    };
+
+    const voteAs = async (
+      account: FundedUser,
+      pollId: anchor.BN,
+      option: number
+    ) => {
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          pollId.toArrayLike(Buffer, "le", 8),
+          account.user.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .vote(pollId, option, new anchor.BN(1_000_000))
+        .accounts({
+          voter: account.user.publicKey,
+          config: configPda,
+          userStats: account.statsPda,
+          poll: pollPdaFor(pollId),
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: account.tokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([account.user])
+        .rpc();
+    };
+
+    const summaryPdaFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("summary"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const closePollAccount = (pollId: anchor.BN) =>
+      program.methods
+        .closePollAccount(pollId)
+        .accounts({
+          creator: creator.user.publicKey,
+          config: configPda,
+          tokenMint: mintPda,
+          poll: pollPdaFor(pollId),
+          pollSummary: summaryPdaFor(pollId),
+          rankedBallots: null,
+          escrowTokenAccount: null,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator.user])
+        .rpc();
+
+    const finalize = (pollId: anchor.BN) =>
+      program.methods
+        .finalizePoll(pollId)
+        .accounts({
+          caller: user1.publicKey,
+          poll: pollPdaFor(pollId),
+        })
+        .signers([user1])
+        .rpc();
+
+    const closeAsCreator = (pollId: anchor.BN) =>
+      program.methods
+        .closePoll(pollId)
+        .accounts({
+          creator: creator.user.publicKey,
+          poll: pollPdaFor(pollId),
+        })
+        .signers([creator.user])
+        .rpc();
+
+    before(async () => {
+      creator = await createFundedUser(50_000_000);
+      voter = await createFundedUser(5_000_000);
+
+      // Two-thirds majority with at least 2 votes
+      winnerPollId = await createBinaryPoll({
+        quorum: { votes: { 0: new anchor.BN(2) } },
+        passThresholdBps: 6667,
+      });
+      await voteAs(creator, winnerPollId, 0);
+      await voteAs(voter, winnerPollId, 0);
+
+      tiePollId = await createBinaryPoll({
+        quorum: null,
+        passThresholdBps: 5000,
+      });
+      await voteAs(creator, tiePollId, 0);
+      await voteAs(voter, tiePollId, 1);
+
+      // Needs 3 votes, gets 2
+      quorumPollId = await createBinaryPoll({
+        quorum: { votes: { 0: new anchor.BN(3) } },
+        passThresholdBps: 5000,
+      });
+      await voteAs(creator, quorumPollId, 0);
+      await voteAs(voter, quorumPollId, 0);
+    });
+
+    it("should fail to create a poll with a zero pass threshold", async () => {
+      try {
+        await createBinaryPoll({ quorum: null, passThresholdBps: 0 });
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidPassRules");
+      }
+    });
+
+    it("should fail to finalize while the poll is active", async () => {
+      try {
+        await finalize(winnerPollId);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollStillActive");
+      }
+    });
+
+    it("should finalize and record the winning option", async () => {
+      await closeAsCreator(winnerPollId);
+      await finalize(winnerPollId);
+
+      const poll = await program.account.poll.fetch(pollPdaFor(winnerPollId));
+      assert.deepEqual(poll.status, { finalized: {} });
+      assert.equal(poll.winningOption, 0);
+      assert.equal(poll.isTie, false);
+      assert.isAbove(poll.finalizedAt.toNumber(), 0);
+      assert.deepEqual(poll.outcome, { passed: {} });
+    });
+
+    it("should record a tie with no winner", async () => {
+      await closeAsCreator(tiePollId);
+      await finalize(tiePollId);
+
+      const poll = await program.account.poll.fetch(pollPdaFor(tiePollId));
+      assert.deepEqual(poll.status, { finalized: {} });
+      assert.isNull(poll.winningOption);
+      assert.equal(poll.isTie, true);
+      assert.deepEqual(poll.outcome, { failed: {} });
+    });
+
+    it("should mark a poll below quorum as QuorumNotMet", async () => {
+      await closeAsCreator(quorumPollId);
+      await finalize(quorumPollId);
+
+      const poll = await program.account.poll.fetch(pollPdaFor(quorumPollId));
+      assert.equal(poll.winningOption, 0);
+      assert.deepEqual(poll.outcome, { quorumNotMet: {} });
+    });
+
+    it("should measure approval support against all voters", async () => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      await program.methods
+        .createPoll(
+          "Which items pass?",
+          ["A", "B", "C"],
+          { fairVote: {} },
+          { approval: {} },
+          60,
+          null,
+          { quorum: null, passThresholdBps: 6667 }
+        )
+        .accounts({
+          creator: creator.user.publicKey,
+          config: configPda,
+          userStats: creator.statsPda,
+          poll: pollPdaFor(pollId),
+          tokenMint: mintPda,
+          creatorTokenAccount: creator.tokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator.user])
+        .rpc();
+
+      // 10 voters: A approved by 9, B by 8, C by 1
+      const masks = [...Array(8).fill(0b011), 0b001, 0b100];
+      for (const mask of masks) {
+        const account = await createFundedUser(5_000_000);
+        const [voteRecordPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("vote"),
+            pollId.toArrayLike(Buffer, "le", 8),
+            account.user.publicKey.toBuffer(),
+          ],
+          program.programId
+        );
+
+        await program.methods
+          .voteApproval(pollId, mask)
+          .accounts({
+            voter: account.user.publicKey,
+            config: configPda,
+            userStats: account.statsPda,
+            poll: pollPdaFor(pollId),
+            voteRecord: voteRecordPda,
+            tokenMint: mintPda,
+            voterTokenAccount: account.tokenAccount,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([account.user])
+          .rpc();
+      }
+
+      await closeAsCreator(pollId);
+      await finalize(pollId);
+
+      // 9 of 10 voters clears two-thirds, though A holds half the approvals
+      const poll = await program.account.poll.fetch(pollPdaFor(pollId));
+      assert.equal(poll.totalVotes.toNumber(), 10);
+      assert.equal(poll.winningOption, 0);
+      assert.deepEqual(poll.outcome, { passed: {} });
+    });
+
+    it("should fail to finalize twice", async () => {
+      try {
+        await finalize(winnerPollId);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyFinalized");
+      }
+    });
+
+    it("should return the outcome in the fixed result layout", async () => {
+      const result = await program.methods
+        .getPollResult(winnerPollId)
+        .accounts({
+          poll: pollPdaFor(winnerPollId),
+        })
+        .view();
+
+      assert.equal(result.pollId.toNumber(), winnerPollId.toNumber());
+      assert.deepEqual(result.status, { finalized: {} });
+      assert.equal(result.hasWinner, true);
+      assert.equal(result.winningOption, 0);
+      assert.equal(result.isTie, false);
+      assert.deepEqual(
+        result.voteCounts.map((count) => count.toNumber()),
+        [2, 0, 0, 0]
+      );
+      assert.deepEqual(result.outcome, { passed: {} });
+    });
+
+    it("should close a vote record after the poll is finalized", async () => {
+      const [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          winnerPollId.toArrayLike(Buffer, "le", 8),
+          voter.user.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      const lamportsBefore = await provider.connection.getBalance(
+        voter.user.publicKey
+      );
+
+      await program.methods
+        .closeVoteRecord(winnerPollId)
+        .accounts({
+          voter: voter.user.publicKey,
+          poll: pollPdaFor(winnerPollId),
+          pollSummary: null,
+          voteRecord: voteRecordPda,
+        })
+        .signers([voter.user])
+        .rpc();
+
+      assert.isNull(
+        await program.account.voteRecord.fetchNullable(voteRecordPda)
+      );
+      assert.isAbove(
+        await provider.connection.getBalance(voter.user.publicKey),
+        lamportsBefore
+      );
+    });
+
+    it("should fail to close a poll account during retention", async () => {
+      try {
+        await closePollAccount(winnerPollId);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("RetentionPeriodActive");
+      }
+    });
+  });
+
+  describe("Archive Poll", () => {
+    // EscrowVote poll finalized in 2023, loaded from tests/fixtures/archived_*
+    // with its escrow emptied and the creator's vote reclaimed
+    const creator = Keypair.fromSeed(new Uint8Array(32).fill(9));
+    const archivedPollId = new anchor.BN(1_000_001);
+    const pollIdBytes = archivedPollId.toArrayLike(Buffer, "le", 8);
+    const [pollPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("poll"), pollIdBytes],
+      program.programId
+    );
+    const [summaryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("summary"), pollIdBytes],
+      program.programId
+    );
+    const [escrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), pollIdBytes],
+      program.programId
+    );
+    const [voteRecordPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote"), pollIdBytes, creator.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const closePollAccount = (escrowTokenAccount: PublicKey | null) =>
+      program.methods
+        .closePollAccount(archivedPollId)
+        .accounts({
+          creator: creator.publicKey,
+          config: configPda,
+          tokenMint: mintPda,
+          poll: pollPda,
+          pollSummary: summaryPda,
+          rankedBallots: null,
+          escrowTokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+    before(async () => {
+      const airdropTx = await provider.connection.requestAirdrop(
+        creator.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropTx);
+    });
+
+    it("should fail to set a zero retention period", async () => {
+      try {
+        await program.methods
+          .updateConfig({
+            ...emptyParams,
+            pollRetentionPeriod: new anchor.BN(0),
+          })
+          .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidConfigValue");
+      }
+    });
+
+    it("should fail to close an escrow poll without its escrow", async () => {
+      try {
+        await closePollAccount(null);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("EscrowAccountRequired");
+      }
+    });
+
+    it("should archive a poll into a summary and close it", async () => {
+      // Tokens sent straight to the escrow must not block archiving
+      const sender = await createFundedUser(1_000_000);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createTransferInstruction(
+            sender.tokenAccount,
+            escrowPda,
+            sender.user.publicKey,
+            1
+          )
+        ),
+        [sender.user]
+      );
+      const supplyBefore = (await provider.connection.getTokenSupply(mintPda))
+        .value.amount;
+
+      await closePollAccount(escrowPda);
+
+      assert.isNull(await program.account.poll.fetchNullable(pollPda));
+      // The stray token is burned and the escrow closed with the poll
+      assert.isNull(await provider.connection.getAccountInfo(escrowPda));
+      assert.equal(
+        (await provider.connection.getTokenSupply(mintPda)).value.amount,
+        (BigInt(supplyBefore) - BigInt(1)).toString()
+      );
+      const summary = await program.account.pollSummary.fetch(summaryPda);
+      assert.deepEqual(summary.status, { finalized: {} });
+      assert.deepEqual(summary.outcome, { passed: {} });
+      assert.equal(summary.winningOption, 0);
+      assert.deepEqual(
+        summary.voteCounts.map((count) => count.toNumber()),
+        [1_000_000, 0, 0, 0]
+      );
+    });
+
+    it("should close a vote record against the poll summary", async () => {
+      await program.methods
+        .closeVoteRecord(archivedPollId)
+        .accounts({
+          voter: creator.publicKey,
+          poll: null,
+          pollSummary: summaryPda,
+          voteRecord: voteRecordPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      assert.isNull(
+        await program.account.voteRecord.fetchNullable(voteRecordPda)
+      );
+    });
+  });
+
+  describe("Migrate Poll", () => {
+    // Pre-status layout account loaded from tests/fixtures/legacy_poll.json:
+    // is_active = true, end_time in the past, vote_counts [3, 1]
+    const legacyPollId = new anchor.BN(1_000_000);
+    const [legacyPollPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("poll"), legacyPollId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    it("should migrate a legacy poll to the status layout", async () => {
+      await program.methods
+        .migratePoll(legacyPollId)
+        .accounts({
+          payer: admin.publicKey,
+          poll: legacyPollPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const poll = await program.account.poll.fetch(legacyPollPda);
+      assert.equal(poll.id.toNumber(), 1_000_000);
+      assert.equal(poll.question, "Legacy poll?");
+      assert.deepEqual(
+        poll.voteCounts.map((count) => count.toNumber()),
+        [3, 1]
+      );
+      // Active past end_time migrates as Expired
+      assert.deepEqual(poll.status, { expired: {} });
+      assert.equal(poll.allowVoteChanges, false);
+      assert.deepEqual(poll.outcome, { undecided: {} });
+    });
+
+    it("should fail to migrate a poll twice", async () => {
+      try {
+        await program.methods
+          .migratePoll(legacyPollId)
+          .accounts({
+            payer: admin.publicKey,
+            poll: legacyPollPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Migrate Config", () => {
+    it("should fail to migrate a current config", async () => {
+      try {
+        await program.methods
+          .migrateConfig()
+          .accounts({
+            admin: admin.publicKey,
+            config: configPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Migrate User Stats", () => {
+    // Pre-redemption layout account loaded from
+    // tests/fixtures/legacy_user_stats.json
+    const legacyWallet = new PublicKey(
+      "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx"
+    );
+    const [legacyStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), legacyWallet.toBuffer()],
+      program.programId
+    );
+
+    it("should migrate legacy user stats to the current layout", async () => {
+      await program.methods
+        .migrateUserStats(legacyWallet)
+        .accounts({
+          payer: admin.publicKey,
+          userStats: legacyStatsPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const stats = await program.account.userStats.fetch(legacyStatsPda);
+      assert.ok(stats.wallet.equals(legacyWallet));
+      assert.equal(stats.totalTokensPurchased.toNumber(), 75_000_000);
+      assert.equal(stats.pollsCreated.toNumber(), 2);
+      assert.equal(stats.votesCast.toNumber(), 5);
+      assert.equal(stats.totalTokensRedeemed.toNumber(), 0);
+      assert.equal(stats.lastRedeemDay.toNumber(), 0);
+    });
+
+    it("should fail to migrate user stats twice", async () => {
+      try {
+        await program.methods
+          .migrateUserStats(legacyWallet)
+          .accounts({
+            payer: admin.publicKey,
+            userStats: legacyStatsPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Migrate Vote Record", () => {
+    // Original layout account loaded from
+    // tests/fixtures/legacy_vote_record.json: 1 mVote on option 0
+    const legacyPollId = new anchor.BN(1_000_000);
+    const legacyVoter = new PublicKey(
+      "US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx"
+    );
+    const [legacyVoteRecordPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vote"),
+        legacyPollId.toArrayLike(Buffer, "le", 8),
+        legacyVoter.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const migrateVoteRecord = () =>
+      program.methods
+        .migrateVoteRecord(legacyPollId, legacyVoter)
+        .accounts({
+          payer: admin.publicKey,
+          voteRecord: legacyVoteRecordPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    it("should migrate a legacy vote record", async () => {
+      await migrateVoteRecord();
+
+      const voteRecord = await program.account.voteRecord.fetch(
+        legacyVoteRecordPda
+      );
+      assert.ok(voteRecord.voter.equals(legacyVoter));
+      assert.equal(voteRecord.pollId.toNumber(), 1_000_000);
+      assert.equal(voteRecord.optionIndex, 0);
+      assert.equal(voteRecord.tokensSpent.toNumber(), 1_000_000);
+      assert.equal(voteRecord.voteCount, 1);
+      assert.deepEqual(
+        voteRecord.optionTokens.map((tokens) => tokens.toNumber()),
+        [1_000_000, 0, 0, 0]
+      );
+      assert.equal(voteRecord.tokensReclaimed, false);
+    });
+
+    it("should fail to migrate a vote record twice", async () => {
+      try {
+        await migrateVoteRecord();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("AlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Cancel Poll", () => {
+    type FundedUser = Awaited<ReturnType<typeof createFundedUser>>;
+
+    let creator: FundedUser;
+    let voter: FundedUser;
+    let fairPollId: anchor.BN;
+    let escrowPollId: anchor.BN;
+
+    const pollPdaFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const escrowPdaFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+    const voteRecordFor = (pollId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          pollId.toArrayLike(Buffer, "le", 8),
+          voter.user.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    const createPollWithMode = async (voteMode: any) => {
+      const config = await program.account.config.fetch(configPda);
+      const pollId = config.totalPollsCreated;
+
+      await program.methods
+        .createPoll(
+          "Poll to be cancelled?",
+          ["Yes", "No"],
+          voteMode,
+          { binary: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: creator.user.publicKey,
+          config: configPda,
+          userStats: creator.statsPda,
+          poll: pollPdaFor(pollId),
+          tokenMint: mintPda,
+          creatorTokenAccount: creator.tokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator.user])
+        .rpc();
+
+      return pollId;
+    };
+
+    const voteOn = (pollId: anchor.BN, escrow: boolean) =>
+      program.methods
+        .vote(pollId, 0, new anchor.BN(1_000_000))
+        .accounts({
+          voter: voter.user.publicKey,
+          config: configPda,
+          userStats: voter.statsPda,
+          poll: pollPdaFor(pollId),
+          voteRecord: voteRecordFor(pollId),
+          tokenMint: mintPda,
+          voterTokenAccount: voter.tokenAccount,
+          escrowTokenAccount: escrow ? escrowPdaFor(pollId) : null,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter.user])
+        .rpc();
+
+    const cancelAs = (signer: Keypair, pollId: anchor.BN) =>
+      program.methods
+        .cancelPoll(pollId)
+        .accounts({
+          creator: signer.publicKey,
+          poll: pollPdaFor(pollId),
+        })
+        .signers([signer])
+        .rpc();
+
+    const claimRefund = (pollId: anchor.BN, escrow: boolean) =>
+      program.methods
+        .claimRefund(pollId)
+        .accounts({
+          voter: voter.user.publicKey,
+          config: configPda,
+          poll: pollPdaFor(pollId),
+          voteRecord: voteRecordFor(pollId),
+          tokenMint: mintPda,
+          escrowTokenAccount: escrow ? escrowPdaFor(pollId) : null,
+          voterTokenAccount: voter.tokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter.user])
+        .rpc();
+
+    const voterBalance = async () =>
+      Number(
+        (await getAccount(provider.connection, voter.tokenAccount)).amount
+      );
+
+    before(async () => {
+      creator = await createFundedUser(35_000_000);
+      voter = await createFundedUser(3_000_000);
+
+      fairPollId = await createPollWithMode({ fairVote: {} });
+      await voteOn(fairPollId, false);
+
+      escrowPollId = await createPollWithMode({ escrowVote: {} });
+      await voteOn(escrowPollId, true);
+    });
+
+    it("should fail to claim a refund before the poll is cancelled", async () => {
+      try {
+        await claimRefund(fairPollId, false);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollNotCancelled");
+      }
+    });
+
+    it("should fail to cancel poll by non-creator", async () => {
+      try {
+        await cancelAs(voter.user, fairPollId);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+    });
+
+    it("should cancel poll by creator", async () => {
+      await cancelAs(creator.user, fairPollId);
+
+      const poll = await program.account.poll.fetch(pollPdaFor(fairPollId));
+      assert.deepEqual(poll.status, { cancelled: {} });
+    });
+
+    it("should fail to close a poll with unclaimed refunds", async () => {
+      const [summaryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("summary"), fairPollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .closePollAccount(fairPollId)
+          .accounts({
+            creator: creator.user.publicKey,
+            config: configPda,
+            tokenMint: mintPda,
+            poll: pollPdaFor(fairPollId),
+            pollSummary: summaryPda,
+            rankedBallots: null,
+            escrowTokenAccount: null,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([creator.user])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnclaimedTokensRemain");
+      }
+    });
+
+    it("should fail to finalize a cancelled poll", async () => {
+      try {
+        await program.methods
+          .finalizePoll(fairPollId)
+          .accounts({
+            caller: user1.publicKey,
+            poll: pollPdaFor(fairPollId),
+          })
+          .signers([user1])
+          .rpc();
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollCancelled");
+      }
+    });
+
+    it("should re-mint burned vote tokens as a refund", async () => {
+      const balanceBefore = await voterBalance();
+
+      await claimRefund(fairPollId, false);
+
+      assert.equal((await voterBalance()) - balanceBefore, 1_000_000);
+      const voteRecord = await program.account.voteRecord.fetch(
+        voteRecordFor(fairPollId)
+      );
+      assert.equal(voteRecord.tokensReclaimed, true);
+    });
+
+    it("should fail to claim a refund twice", async () => {
+      try {
+        await claimRefund(fairPollId, false);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("TokensAlreadyReclaimed");
+      }
+    });
+
+    it("should return escrowed tokens as a refund", async () => {
+      await cancelAs(creator.user, escrowPollId);
+      const balanceBefore = await voterBalance();
+
+      await claimRefund(escrowPollId, true);
+
+      assert.equal((await voterBalance()) - balanceBefore, 1_000_000);
+      const escrow = await getAccount(
+        provider.connection,
+        escrowPdaFor(escrowPollId)
+      );
+      assert.equal(Number(escrow.amount), 0);
+    });
+
+    it("should fail to cancel a poll after voting has ended", async () => {
+      const closedPollId = await createPollWithMode({ fairVote: {} });
+      await program.methods
+        .closePoll(closedPollId)
+        .accounts({
+          creator: creator.user.publicKey,
+          poll: pollPdaFor(closedPollId),
+        })
+        .signers([creator.user])
+        .rpc();
+
+      try {
+        await cancelAs(creator.user, closedPollId);
+        assert.fail("Should have failed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PollNotActive");
+      }
+    });
+  });
+
+  describe("Events", () => {
+    type FundedUser = Awaited<ReturnType<typeof createFundedUser>>;
+
+    let creator: FundedUser;
+    let voter: FundedUser;
+    let pollId: anchor.BN;
+    let pollPda: PublicKey;
+    let voteRecordPda: PublicKey;
+
+    const eventParser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+
+    const eventsOf = async (signature: string) => {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(tx.meta.logMessages)];
+    };
+
+    before(async () => {
+      creator = await createFundedUser(20_000_000);
+      voter = await createFundedUser(5_000_000);
+    });
+
+    it("Emits TokensPurchased with the cost and daily total", async () => {
+      const signature = await program.methods
+        .purchaseTokens(new anchor.BN(1_000_000))
+        .accounts({
+          buyer: voter.user.publicKey,
+          config: configPda,
+          userStats: voter.statsPda,
+          tokenMint: mintPda,
+          buyerTokenAccount: voter.tokenAccount,
+          solVault: vaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter.user])
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await eventsOf(signature);
+      const config = await program.account.config.fetch(configPda);
+      expect(event.name).to.equal("tokensPurchased");
+      expect(event.data.buyer.toString()).to.equal(
+        voter.user.publicKey.toString()
+      );
+      expect(Number(event.data.amount)).to.equal(1_000_000);
+      expect(Number(event.data.solUsdRate)).to.equal(Number(config.solUsdRate));
+      expect(Number(event.data.solCost)).to.be.greaterThan(0);
+      expect(Number(event.data.tokensPurchasedToday)).to.equal(6_000_000);
+    });
+
+    it("Emits PollCreated with the poll PDA and schedule", async () => {
+      const config = await program.account.config.fetch(configPda);
+      pollId = config.totalPollsCreated;
+      [pollPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("poll"), pollId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      [voteRecordPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vote"),
+          pollId.toArrayLike(Buffer, "le", 8),
+          voter.user.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      const signature = await program.methods
+        .createPoll(
+          "Are events emitted?",
+          ["Yes", "No"],
+          { fairVote: {} },
+          { binary: {} },
+          60,
+          null,
+          null
+        )
+        .accounts({
+          creator: creator.user.publicKey,
+          config: configPda,
+          userStats: creator.statsPda,
+          poll: pollPda,
+          tokenMint: mintPda,
+          creatorTokenAccount: creator.tokenAccount,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator.user])
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await eventsOf(signature);
+      expect(event.name).to.equal("pollCreated");
+      expect(Number(event.data.pollId)).to.equal(Number(pollId));
+      expect(event.data.poll.toString()).to.equal(pollPda.toString());
+      expect(event.data.options).to.deep.equal(["Yes", "No"]);
+      expect(event.data.status).to.deep.equal({ active: {} });
+      const duration =
+        Number(event.data.endTime) - Number(event.data.startTime);
+      expect(duration).to.equal(3600);
+      expect(Number(event.data.creationCost)).to.equal(
+        Number(config.pollCreationCost)
+      );
+    });
+
+    it("Emits VoteCast with the vote record and weight", async () => {
+      const signature = await program.methods
+        .vote(pollId, 1, new anchor.BN(1_000_000))
+        .accounts({
+          voter: voter.user.publicKey,
+          config: configPda,
+          userStats: voter.statsPda,
+          poll: pollPda,
+          voteRecord: voteRecordPda,
+          tokenMint: mintPda,
+          voterTokenAccount: voter.tokenAccount,
+          escrowTokenAccount: null,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([voter.user])
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await eventsOf(signature);
+      const config = await program.account.config.fetch(configPda);
+      expect(event.name).to.equal("voteCast");
+      expect(event.data.voteRecord.toString()).to.equal(
+        voteRecordPda.toString()
+      );
+      expect(event.data.optionIndex).to.equal(1);
+      expect(event.data.optionMask).to.equal(0b10);
+      expect(Number(event.data.tokensSpent)).to.equal(Number(config.voteCost));
+      expect(Number(event.data.voteWeight)).to.equal(1);
+    });
+
+    it("Emits PollClosed with the final counts", async () => {
+      const signature = await program.methods
+        .closePoll(pollId)
+        .accounts({
+          creator: creator.user.publicKey,
+          poll: pollPda,
+        })
+        .signers([creator.user])
+        .rpc({ commitment: "confirmed" });
+
+      const [event] = await eventsOf(signature);
+      expect(event.name).to.equal("pollClosed");
+      expect(event.data.creator.toString()).to.equal(
+        creator.user.publicKey.toString()
+      );
+      expect(event.data.voteCounts.map(Number)).to.deep.equal([0, 1]);
+      expect(Number(event.data.totalVotes)).to.equal(1);
+    });
+  });
+
   describe("Close Poll", () => {
     let testPollId: anchor.BN;
 
@@ -868,7 +3861,9 @@ describe("mvote", () => {
           ["Yes", "No"],
           { fairVote: {} },
           { binary: {} },
-          60
+          60,
+          null,
+          null
         )
         .accounts({
           creator: user1.publicKey,
@@ -900,7 +3895,7 @@ describe("mvote", () => {
         .rpc();
 
       const poll = await program.account.poll.fetch(pollPda);
-      assert.equal(poll.isActive, false);
+      assert.deepEqual(poll.status, { closedEarly: {} });
     });
 
     it("should fail to close poll by non-creator", async () => {
@@ -935,7 +3930,9 @@ describe("mvote", () => {
           ["Yes", "No"],
           { fairVote: {} },
           { binary: {} },
-          60
+          60,
+          null,
+          null
         )
         .accounts({
           creator: user1.publicKey,
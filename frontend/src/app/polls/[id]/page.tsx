@@ -9,6 +9,7 @@ import Link from "next/link";
 import { VoteForm } from "@/components/polls/VoteForm";
 import {
   getPollPda,
+  getPollStatus,
   Poll,
   isVoteModeFair,
   isVotingStyleBinary,
@@ -78,7 +79,8 @@ export default function PollDetailPage() {
 
   const now = Date.now() / 1000;
   const endTime = poll.endTime.toNumber();
-  const isExpired = now > endTime || !poll.isActive;
+  const status = getPollStatus(poll, now);
+  const isExpired = status !== "scheduled" && status !== "active";
   const timeLeft = Math.max(0, endTime - now);
 
   const formatTimeLeft = (seconds: number) => {
@@ -203,12 +205,25 @@ export default function PollDetailPage() {
         </div>
       </div>
 
-      {!isExpired && <VoteForm poll={poll} pollId={pollId} onVoted={fetchPoll} />}
+      {status === "active" && (
+        <VoteForm poll={poll} pollId={pollId} onVoted={fetchPoll} />
+      )}
+
+      {status === "scheduled" && (
+        <div className="bg-gray-800 rounded-xl p-6 text-center">
+          <p className="text-gray-400">
+            Voting opens{" "}
+            {new Date(poll.startTime.toNumber() * 1000).toLocaleString()}.
+          </p>
+        </div>
+      )}
 
       {isExpired && (
         <div className="bg-gray-800 rounded-xl p-6 text-center">
           <p className="text-gray-400">
-            This poll has ended. Voting is no longer available.
+            {status === "cancelled"
+              ? "This poll was cancelled. Voting is no longer available."
+              : "This poll has ended. Voting is no longer available."}
           </p>
         </div>
       )}
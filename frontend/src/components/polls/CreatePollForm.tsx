@@ -15,6 +15,7 @@ import {
   getPollPda,
   VoteMode,
   VotingStyle,
+  PassRules,
   Config,
 } from "@/lib/anchor/program";
 
@@ -31,6 +32,11 @@ export const CreatePollForm = () => {
     "binary"
   );
   const [duration, setDuration] = useState(60);
+  // Empty start time opens the poll immediately
+  const [startTime, setStartTime] = useState("");
+  // Empty threshold creates a poll without pass rules
+  const [passThreshold, setPassThreshold] = useState("");
+  const [quorumVotes, setQuorumVotes] = useState("");
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState("");
 
@@ -77,6 +83,32 @@ export const CreatePollForm = () => {
       return;
     }
 
+    const startTimeArg = startTime
+      ? new BN(Math.floor(new Date(startTime).getTime() / 1000))
+      : null;
+    if (startTimeArg && startTimeArg.toNumber() < Date.now() / 1000) {
+      setError("Start time must be in the future");
+      return;
+    }
+
+    let passRulesArg: PassRules | null = null;
+    if (passThreshold) {
+      const threshold = Number(passThreshold);
+      if (!(threshold > 0 && threshold <= 100)) {
+        setError("Pass threshold must be between 0 and 100%");
+        return;
+      }
+      const quorum = Number(quorumVotes);
+      if (quorumVotes && !(Number.isInteger(quorum) && quorum > 0)) {
+        setError("Minimum votes must be a positive whole number");
+        return;
+      }
+      passRulesArg = {
+        quorum: quorumVotes ? { votes: { 0: new BN(quorum) } } : null,
+        passThresholdBps: Math.round(threshold * 100),
+      };
+    }
+
     setLoading(true);
 
     try {
@@ -99,7 +131,15 @@ export const CreatePollForm = () => {
         votingStyle === "binary" ? { binary: {} } : { multipleChoice: {} };
 
       await program.methods
-        .createPoll(question, validOptions, voteModeArg, votingStyleArg, duration)
+        .createPoll(
+          question,
+          validOptions,
+          voteModeArg,
+          votingStyleArg,
+          duration,
+          startTimeArg,
+          passRulesArg
+        )
         .accounts({
           creator: publicKey,
           config: configPda,
@@ -242,6 +282,55 @@ export const CreatePollForm = () => {
         </div>
       </div>
 
+      <div>
+        <label className="block text-sm font-medium text-gray-300 mb-2">
+          Start Time (optional)
+        </label>
+        <input
+          type="datetime-local"
+          value={startTime}
+          onChange={(e) => setStartTime(e.target.value)}
+          className="w-full bg-gray-700 border border-gray-600 rounded-lg px-4 py-2 text-white focus:outline-none focus:border-purple-500"
+        />
+        <p className="text-gray-500 text-sm mt-1">
+          Leave empty to open voting immediately
+        </p>
+      </div>
+
+      <div className="grid grid-cols-2 gap-4">
+        <div>
+          <label className="block text-sm font-medium text-gray-300 mb-2">
+            Pass Threshold % (optional)
+          </label>
+          <input
+            type="number"
+            min={0.01}
+            max={100}
+            step={0.01}
+            value={passThreshold}
+            onChange={(e) => setPassThreshold(e.target.value)}
+            className="w-full bg-gray-700 border border-gray-600 rounded-lg px-4 py-2 text-white focus:outline-none focus:border-purple-500"
+            placeholder="e.g. 50"
+          />
+        </div>
+
+        <div>
+          <label className="block text-sm font-medium text-gray-300 mb-2">
+            Minimum Votes (optional)
+          </label>
+          <input
+            type="number"
+            min={1}
+            step={1}
+            value={quorumVotes}
+            onChange={(e) => setQuorumVotes(e.target.value)}
+            disabled={!passThreshold}
+            className="w-full bg-gray-700 border border-gray-600 rounded-lg px-4 py-2 text-white focus:outline-none focus:border-purple-500 disabled:opacity-50"
+            placeholder="No quorum"
+          />
+        </div>
+      </div>
+
       <div className="bg-gray-700/50 rounded-lg p-4">
         <div className="flex justify-between items-center">
           <span className="text-gray-300">Creation Cost</span>
@@ -1,7 +1,12 @@
 "use client";
 
 import Link from "next/link";
-import { Poll, isVoteModeFair, formatTokenAmount } from "@/lib/anchor/program";
+import {
+  Poll,
+  getPollStatus,
+  isVoteModeFair,
+  formatTokenAmount,
+} from "@/lib/anchor/program";
 
 interface Props {
   poll: Poll;
@@ -11,7 +16,8 @@ interface Props {
 export const PollCard = ({ poll, pollId }: Props) => {
   const now = Date.now() / 1000;
   const endTime = poll.endTime.toNumber();
-  const isExpired = now > endTime || !poll.isActive;
+  const status = getPollStatus(poll, now);
+  const isExpired = status !== "scheduled" && status !== "active";
   const timeLeft = Math.max(0, endTime - now);
 
   const formatTimeLeft = (seconds: number) => {
@@ -8,6 +8,7 @@ import {
   getProgram,
   getConfigPda,
   getPollPda,
+  getPollStatus,
   Poll,
   Config,
 } from "@/lib/anchor/program";
@@ -76,11 +77,11 @@ export const PollList = () => {
   }, [connection]);
 
   const filteredPolls = polls.filter(({ poll }) => {
-    const now = Date.now() / 1000;
-    const isActive = poll.isActive && poll.endTime.toNumber() > now;
+    const status = getPollStatus(poll);
+    const isOpen = status === "scheduled" || status === "active";
 
-    if (filter === "active") return isActive;
-    if (filter === "ended") return !isActive;
+    if (filter === "active") return status === "active";
+    if (filter === "ended") return !isOpen;
     return true;
   });
 
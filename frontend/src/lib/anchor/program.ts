@@ -54,8 +54,41 @@ export const getProgram = (provider: AnchorProvider) => {
 };
 
 // Types
-export type VoteMode = { fairVote: {} } | { holdingVote: {} };
-export type VotingStyle = { binary: {} } | { multipleChoice: {} };
+export type VoteMode =
+  | { fairVote: {} }
+  | { holdingVote: {} }
+  | { escrowVote: {} }
+  | { quadratic: {} };
+export type VotingStyle =
+  | { binary: {} }
+  | { multipleChoice: {} }
+  | { rankedChoice: {} }
+  | { approval: {} };
+export type PollStatus =
+  | { scheduled: {} }
+  | { active: {} }
+  | { closedEarly: {} }
+  | { expired: {} }
+  | { finalized: {} }
+  | { cancelled: {} };
+export type PollStatusName =
+  | "scheduled"
+  | "active"
+  | "closedEarly"
+  | "expired"
+  | "finalized"
+  | "cancelled";
+export type PollOutcome =
+  | { undecided: {} }
+  | { passed: {} }
+  | { failed: {} }
+  | { quorumNotMet: {} };
+export type Quorum = { votes: { 0: BN } } | { tokens: { 0: BN } };
+
+export interface PassRules {
+  quorum: Quorum | null;
+  passThresholdBps: number;
+}
 
 export interface Config {
   admin: PublicKey;
@@ -67,6 +100,17 @@ export interface Config {
   pollCreationCost: BN;
   voteCost: BN;
   totalPollsCreated: BN;
+  pendingAdmin: PublicKey;
+  treasury: PublicKey;
+  paused: boolean;
+  priceOracle: PublicKey;
+  priceFeedId: number[];
+  maxPriceAge: BN;
+  maxPriceConfBps: number;
+  redeemSpreadBps: number;
+  dailyRedeemLimit: BN;
+  maxStartLeadTime: BN;
+  pollRetentionPeriod: BN;
   bump: number;
 }
 
@@ -77,6 +121,9 @@ export interface UserStats {
   totalTokensPurchased: BN;
   pollsCreated: BN;
   votesCast: BN;
+  tokensRedeemedToday: BN;
+  lastRedeemDay: BN;
+  totalTokensRedeemed: BN;
   bump: number;
 }
 
@@ -92,7 +139,22 @@ export interface Poll {
   endTime: BN;
   totalVotes: BN;
   totalTokensSpent: BN;
-  isActive: boolean;
+  status: PollStatus;
+  allowVoteChanges: boolean;
+  isTallied: boolean;
+  winningOption: number | null;
+  tallyRounds: BN[][];
+  isSecret: boolean;
+  revealEndTime: BN;
+  committedVotes: BN;
+  revealedVotes: BN;
+  isTie: boolean;
+  finalizedAt: BN;
+  cancelledAt: BN;
+  quorum: Quorum | null;
+  passThresholdBps: number;
+  outcome: PollOutcome;
+  outstandingRecords: BN;
   bump: number;
 }
 
@@ -102,6 +164,13 @@ export interface VoteRecord {
   optionIndex: number;
   tokensSpent: BN;
   votedAt: BN;
+  voteCount: number;
+  optionTokens: BN[];
+  tokensReclaimed: boolean;
+  rankings: Buffer;
+  optionMask: number;
+  commitment: number[];
+  isRevealed: boolean;
   bump: number;
 }
 
@@ -122,3 +191,16 @@ export const isVoteModeFair = (mode: VoteMode): boolean => {
 export const isVotingStyleBinary = (style: VotingStyle): boolean => {
   return "binary" in style;
 };
+
+// Status with the on-chain time-based transitions applied: Scheduled becomes
+// Active at startTime, and Scheduled or Active becomes Expired after endTime
+export const getPollStatus = (
+  poll: Poll,
+  now: number = Date.now() / 1000
+): PollStatusName => {
+  const status = Object.keys(poll.status)[0] as PollStatusName;
+  if (status !== "scheduled" && status !== "active") return status;
+  if (now > poll.endTime.toNumber()) return "expired";
+  if (now >= poll.startTime.toNumber()) return "active";
+  return "scheduled";
+};